@@ -0,0 +1,289 @@
+//! Filename templates for recordings and replays.
+//!
+//! Templates use the same time specifiers as the filename formatting of OBS Studio,
+//! for example `%CCYY-%MM-%DD %hh-%mm-%ss`, and custom tokens in braces like `{scene}`.
+//! The builders of this crate set the following tokens every time an output is started:
+//! - `{output}` - The name of the output
+//! - `{scene}` - The name of the scene on output channel 0, empty if there is none
+//!
+//! # Example
+//!
+//! ```no_run
+//! use libobs_simple::output::filename::FilenameTemplate;
+//!
+//! let template = FilenameTemplate::new("{game} {scene} %CCYY-%MM-%DD %hh-%mm-%ss")
+//!     .set_token("game", "Minecraft")
+//!     .set_allow_spaces(false);
+//! ```
+
+use std::{
+    collections::HashMap,
+    ffi::CStr,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Local};
+use libobs_wrapper::{run_with_obs, runtime::ObsRuntime, utils::ObsError};
+
+/// Time specifiers of OBS Studio that are not part of strftime and their strftime counterparts.
+/// Longer specifiers come first, so `%MM` is not mistaken for `%M`.
+const OBS_TIME_SPECIFIERS: [(&str, &str); 7] = [
+    ("CCYY", "%Y"),
+    ("YY", "%y"),
+    ("MM", "%m"),
+    ("DD", "%d"),
+    ("hh", "%H"),
+    ("mm", "%M"),
+    ("ss", "%S"),
+];
+
+/// Single character strftime specifiers OBS Studio supports in filenames.
+const STRFTIME_SPECIFIERS: &str = "aAbBdHImMpSyYzZ";
+
+/// Characters that are not allowed in filenames on at least one platform
+const INVALID_FILENAME_CHARS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// A template for the names of recorded files, see the module documentation for the syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilenameTemplate {
+    format: String,
+    tokens: HashMap<String, String>,
+    allow_spaces: bool,
+}
+
+impl Default for FilenameTemplate {
+    /// The default filename format of OBS Studio
+    fn default() -> Self {
+        Self::new("%CCYY-%MM-%DD %hh-%mm-%ss")
+    }
+}
+
+impl FilenameTemplate {
+    pub fn new<S: Into<String>>(format: S) -> Self {
+        Self {
+            format: format.into(),
+            tokens: HashMap::new(),
+            allow_spaces: true,
+        }
+    }
+
+    /// Sets the value of the token `{name}`. Characters that are not allowed in filenames
+    /// are replaced with underscores and `%` is escaped, so the value is never read as a time specifier.
+    pub fn set_token<K: Into<String>, V: AsRef<str>>(mut self, name: K, value: V) -> Self {
+        let mut escaped = String::with_capacity(value.as_ref().len());
+        for c in value.as_ref().chars() {
+            match c {
+                '%' => escaped.push_str("%%"),
+                c if INVALID_FILENAME_CHARS.contains(&c) => escaped.push('_'),
+                c => escaped.push(c),
+            }
+        }
+
+        self.tokens.insert(name.into(), escaped);
+        self
+    }
+
+    /// Sets whether the filename may contain spaces, otherwise they are replaced with underscores.
+    pub fn set_allow_spaces(mut self, allow: bool) -> Self {
+        self.allow_spaces = allow;
+        self
+    }
+
+    pub fn format(&self) -> &str {
+        &self.format
+    }
+
+    pub fn allow_spaces(&self) -> bool {
+        self.allow_spaces
+    }
+
+    /// Replaces the custom tokens but keeps the time specifiers, so OBS can format the time
+    /// itself, for example when it splits a recording. Unknown tokens are kept as they are.
+    pub fn expand_tokens(&self) -> String {
+        let mut result = String::with_capacity(self.format.len());
+        let mut rest = self.format.as_str();
+
+        while let Some(start) = rest.find('{') {
+            result.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let value = rest
+                .find('}')
+                .and_then(|end| self.tokens.get(&rest[1..end]).map(|value| (end, value)));
+            match value {
+                Some((end, value)) => {
+                    result.push_str(value);
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    result.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+
+        result.push_str(rest);
+        result
+    }
+
+    /// Formats the filename (without extension) for the given time.
+    pub fn render(&self, time: &DateTime<Local>) -> String {
+        let format = self.expand_tokens();
+        let mut result = String::with_capacity(format.len());
+        let mut rest = format.as_str();
+
+        while let Some(start) = rest.find('%') {
+            result.push_str(&rest[..start]);
+            let spec = &rest[start + 1..];
+
+            if let Some(after) = spec.strip_prefix('%') {
+                result.push('%');
+                rest = after;
+                continue;
+            }
+
+            if let Some((obs_spec, strftime)) = OBS_TIME_SPECIFIERS
+                .iter()
+                .find(|(obs_spec, _)| spec.starts_with(obs_spec))
+            {
+                result.push_str(&time.format(strftime).to_string());
+                rest = &spec[obs_spec.len()..];
+                continue;
+            }
+
+            match spec.chars().next() {
+                Some(c) if STRFTIME_SPECIFIERS.contains(c) => {
+                    result.push_str(&time.format(&format!("%{}", c)).to_string());
+                    rest = &spec[c.len_utf8()..];
+                }
+                _ => {
+                    result.push('%');
+                    rest = spec;
+                }
+            }
+        }
+
+        result.push_str(rest);
+        if !self.allow_spaces {
+            result = result.replace(' ', "_");
+        }
+
+        result
+    }
+
+    /// Returns the path of a new file in `directory` for the current time.
+    /// If the file exists already, a number is appended like OBS Studio does it, e.g. `name (2).mp4`.
+    pub fn next_path<P: AsRef<Path>>(&self, directory: P, extension: &str) -> PathBuf {
+        let name = self.render(&Local::now());
+        let directory = directory.as_ref();
+
+        let file_name = |suffix: Option<u32>| {
+            let mut file_name = name.clone();
+            if let Some(suffix) = suffix {
+                let separator = if self.allow_spaces { " " } else { "_" };
+                file_name.push_str(&format!("{}({})", separator, suffix));
+            }
+            if !extension.is_empty() {
+                file_name.push('.');
+                file_name.push_str(extension);
+            }
+
+            directory.join(file_name)
+        };
+
+        let mut path = file_name(None);
+        let mut suffix = 2;
+        while path.exists() {
+            path = file_name(Some(suffix));
+            suffix += 1;
+        }
+
+        path
+    }
+
+    /// Sets the tokens the builders of this crate provide, see the module documentation.
+    pub(crate) fn with_output_tokens(
+        &self,
+        output_name: &str,
+        runtime: &ObsRuntime,
+    ) -> Result<Self, ObsError> {
+        let scene = current_scene_name(runtime)?.unwrap_or_default();
+
+        Ok(self
+            .clone()
+            .set_token("output", output_name)
+            .set_token("scene", scene))
+    }
+}
+
+/// Returns the name of the source on output channel 0, which is usually the current scene.
+fn current_scene_name(runtime: &ObsRuntime) -> Result<Option<String>, ObsError> {
+    run_with_obs!(runtime, move || {
+        unsafe {
+            // Safety: The source is checked for null and released after its name was copied.
+            let source = libobs::obs_get_output_source(0);
+            if source.is_null() {
+                return None;
+            }
+
+            let name = libobs::obs_source_get_name(source);
+            let name = if name.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(name).to_string_lossy().to_string())
+            };
+
+            libobs::obs_source_release(source);
+            name
+        }
+    })
+}
+
+#[test]
+fn test_filename_template() {
+    use chrono::TimeZone;
+
+    let time = Local.with_ymd_and_hms(2024, 1, 31, 9, 5, 7).unwrap();
+
+    let template = FilenameTemplate::new("{game} %CCYY-%MM-%DD %hh-%mm-%ss %% %q {unknown}")
+        .set_token("game", "Half-Life: Alyx");
+    assert_eq!(
+        template.expand_tokens(),
+        "Half-Life_ Alyx %CCYY-%MM-%DD %hh-%mm-%ss %% %q {unknown}"
+    );
+    assert_eq!(
+        template.render(&time),
+        "Half-Life_ Alyx 2024-01-31 09-05-07 % %q {unknown}"
+    );
+
+    let template = FilenameTemplate::new("%YY %Y%m%d %H-%M").set_allow_spaces(false);
+    assert_eq!(template.render(&time), "24_20240131_09-05");
+
+    // Existing files are not overwritten
+    let directory = std::env::temp_dir().join("libobs_filename_template_test");
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let template = FilenameTemplate::new("recording");
+    let first = template.next_path(&directory, "mkv");
+    assert_eq!(first, directory.join("recording.mkv"));
+
+    std::fs::write(&first, []).unwrap();
+    assert_eq!(
+        template.next_path(&directory, "mkv"),
+        directory.join("recording (2).mkv")
+    );
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_filename_template_escapes_tokens() {
+    use chrono::TimeZone;
+
+    let time = Local.with_ymd_and_hms(2024, 1, 31, 9, 5, 7).unwrap();
+
+    let template = FilenameTemplate::new("{scene} %MM").set_token("scene", "100%MM");
+    assert_eq!(template.expand_tokens(), "100%%MM %MM");
+    assert_eq!(template.render(&time), "100%MM 01");
+}
@@ -1,2 +1,3 @@
+pub mod filename;
 pub mod replay;
 pub mod simple;
@@ -39,14 +39,17 @@
 use libobs_wrapper::{
     context::ObsContext,
     data::{
-        output::{ObsOutputTrait, ObsReplayBufferOutputRef},
-        ObsData, ObsDataGetters, ObsDataSetters,
+        output::{ObsOutputStartHook, ObsOutputTrait, ObsReplayBufferOutputRef},
+        ObsData, ObsDataGetters, ObsDataPointers, ObsDataSetters,
     },
     encoders::{ObsAudioEncoderType, ObsContextEncoders, ObsVideoEncoderType},
     utils::{AudioEncoderInfo, ObsError, ObsPath, ObsString, OutputInfo, VideoEncoderInfo},
 };
 
-use super::simple::{AudioEncoder, HardwareCodec, HardwarePreset, VideoEncoder, X264Preset};
+use super::{
+    filename::FilenameTemplate,
+    simple::{AudioEncoder, HardwareCodec, HardwarePreset, VideoEncoder, X264Preset},
+};
 
 /// Settings for replay buffer output
 #[derive(Debug)]
@@ -68,6 +71,7 @@ pub struct ReplayBufferSettings {
     audio_bitrate: u32,
     directory: ObsPath,
     custom_encoder_settings: Option<String>,
+    filename_template: Option<FilenameTemplate>,
 }
 
 impl ReplayBufferSettings {
@@ -142,6 +146,13 @@ impl ReplayBufferSettings {
         self.audio_encoder = encoder;
         self
     }
+
+    /// Names the saved replays using the given template, which replaces the format
+    /// and whether spaces are allowed.
+    pub fn with_filename_template(mut self, template: FilenameTemplate) -> Self {
+        self.filename_template = Some(template);
+        self
+    }
 }
 
 /// Builder for replay buffer outputs
@@ -191,6 +202,7 @@ impl ReplayBufferBuilder {
                 video_encoder: VideoEncoder::X264(X264Preset::VeryFast),
                 audio_encoder: AudioEncoder::AAC,
                 custom_encoder_settings: None,
+                filename_template: None,
             },
             context,
         }
@@ -232,6 +244,14 @@ impl ReplayBufferBuilder {
         self
     }
 
+    /// Names the saved replays using the given template, which replaces the format
+    /// and whether spaces are allowed. The tokens are updated every time the replay buffer
+    /// is started, the time is the time the replay is saved.
+    pub fn filename_template(mut self, template: FilenameTemplate) -> Self {
+        self.settings.filename_template = Some(template);
+        self
+    }
+
     /// Sets the video bitrate in Kbps.
     pub fn video_bitrate(mut self, bitrate: u32) -> Self {
         self.settings.video_bitrate = bitrate;
@@ -274,10 +294,23 @@ impl ReplayBufferBuilder {
         let mut output_settings = self.context.data()?;
         output_settings.set_int("max_time_sec", self.settings.max_time_sec)?;
         output_settings.set_int("max_size_mb", self.settings.max_size_mb)?;
-        output_settings.set_string("format", self.settings.format.clone())?;
         output_settings.set_string("extension", self.settings.extension.clone())?;
         output_settings.set_string("directory", self.settings.directory.clone().build())?;
-        output_settings.set_bool("allow_spaces", self.settings.allow_spaces)?;
+        match &self.settings.filename_template {
+            Some(template) => {
+                // OBS formats the time itself when the replay is saved
+                let template = template.with_output_tokens(
+                    &self.settings.name.to_string(),
+                    output_settings.runtime(),
+                )?;
+                output_settings.set_string("format", template.expand_tokens().as_str())?;
+                output_settings.set_bool("allow_spaces", template.allow_spaces())?;
+            }
+            None => {
+                output_settings.set_string("format", self.settings.format.clone())?;
+                output_settings.set_bool("allow_spaces", self.settings.allow_spaces)?;
+            }
+        }
 
         log::trace!(
             "Replay buffer output settings: {:?}",
@@ -293,6 +326,14 @@ impl ReplayBufferBuilder {
         );
 
         let mut output = self.context.replay_buffer(output_info)?;
+        if let Some(template) = self.settings.filename_template.clone() {
+            let output_name = self.settings.name.to_string();
+            output.set_start_hook(Some(ObsOutputStartHook::new(move |settings| {
+                let template = template.with_output_tokens(&output_name, settings.runtime())?;
+                settings.set_string("format", template.expand_tokens().as_str())?;
+                Ok(())
+            })))?;
+        }
 
         // Create and configure video encoder (with hardware fallback)
         let video_encoder_type = self.select_video_encoder_type(&self.settings.video_encoder)?;
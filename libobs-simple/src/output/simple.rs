@@ -35,16 +35,22 @@
 //! }
 //! ```
 
+use std::{collections::HashMap, path::PathBuf};
+
 use libobs_wrapper::{
     context::ObsContext,
     data::{
-        output::{ObsOutputRef, ObsOutputTrait},
-        ObsData, ObsDataSetters,
+        output::{ObsOutputRef, ObsOutputStartHook, ObsOutputTrait},
+        ObsData, ObsDataPointers, ObsDataSetters,
     },
     encoders::{ObsAudioEncoderType, ObsContextEncoders, ObsVideoEncoderType},
+    enums::ObsAudioTracks,
+    sources::ObsSourceTrait,
     utils::{AudioEncoderInfo, ObsError, ObsPath, ObsString, OutputInfo, VideoEncoderInfo},
 };
 
+use super::filename::FilenameTemplate;
+
 /// Preset for x264 software encoder
 #[derive(Debug, Clone, Copy)]
 pub enum X264Preset {
@@ -178,6 +184,21 @@ pub enum OutputFormat {
     MpegTs,
 }
 
+impl OutputFormat {
+    /// Returns the file extension of this format without the leading dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::FlashVideo => "flv",
+            OutputFormat::MatroskaVideo => "mkv",
+            OutputFormat::Mpeg4 | OutputFormat::HybridMP4 | OutputFormat::FragmentedMP4 => "mp4",
+            OutputFormat::QuickTime | OutputFormat::HybridMov | OutputFormat::FragmentedMOV => {
+                "mov"
+            }
+            OutputFormat::MpegTs => "ts",
+        }
+    }
+}
+
 /// Unified output settings
 #[derive(Debug)]
 pub struct OutputSettings {
@@ -187,10 +208,16 @@ pub struct OutputSettings {
     rate_control: RateControl,
     video_encoder: VideoEncoder,
     audio_encoder: AudioEncoder,
+    audio_tracks: ObsAudioTracks,
+    audio_track_names: HashMap<usize, String>,
     custom_encoder_settings: Option<String>,
     path: ObsPath,
     format: OutputFormat,
     custom_muxer_settings: Option<String>,
+    split_max_time_sec: u32,
+    split_max_size_mb: u32,
+    split_manually: bool,
+    filename_template: Option<FilenameTemplate>,
 }
 
 impl OutputSettings {
@@ -260,12 +287,54 @@ impl OutputSettings {
         self.rate_control = rate_control;
         self
     }
+
+    /// Sets the audio tracks to record, each one as its own audio stream.
+    pub fn with_audio_tracks(mut self, tracks: ObsAudioTracks) -> Self {
+        self.audio_tracks = tracks;
+        self
+    }
+
+    /// Sets the title of the audio stream of the given track (1-6).
+    pub fn with_audio_track_name<S: Into<String>>(mut self, track: usize, name: S) -> Self {
+        self.audio_track_names.insert(track, name.into());
+        self
+    }
+
+    /// Starts a new file after the given number of seconds, 0 disables splitting by time.
+    pub fn with_split_max_time_sec(mut self, seconds: u32) -> Self {
+        self.split_max_time_sec = seconds;
+        self
+    }
+
+    /// Starts a new file after the given size in megabytes, 0 disables splitting by size.
+    pub fn with_split_max_size_mb(mut self, megabytes: u32) -> Self {
+        self.split_max_size_mb = megabytes;
+        self
+    }
+
+    /// Allows splitting the recording manually with `ObsOutputTrait::split_file`.
+    pub fn with_split_manually(mut self, enabled: bool) -> Self {
+        self.split_manually = enabled;
+        self
+    }
+
+    /// Names the recordings using the given template. The output path is used as the directory
+    /// the recordings are saved to and every start of the output records to a new file.
+    pub fn with_filename_template(mut self, template: FilenameTemplate) -> Self {
+        self.filename_template = Some(template);
+        self
+    }
+
+    fn is_splitting(&self) -> bool {
+        self.split_manually || self.split_max_time_sec > 0 || self.split_max_size_mb > 0
+    }
 }
 
 #[derive(Debug)]
 pub struct SimpleOutputBuilder {
     settings: OutputSettings,
     context: ObsContext,
+    source_audio_tracks: Vec<(Box<dyn ObsSourceTrait>, ObsAudioTracks)>,
 }
 
 pub trait ObsContextSimpleExt {
@@ -300,13 +369,20 @@ impl SimpleOutputBuilder {
                 rate_control: RateControl::default(),
                 video_encoder: VideoEncoder::X264(X264Preset::VeryFast),
                 audio_encoder: AudioEncoder::AAC,
+                audio_tracks: ObsAudioTracks::TRACK_1,
+                audio_track_names: HashMap::new(),
                 custom_encoder_settings: None,
                 path: path.into(),
                 format: OutputFormat::default(),
                 custom_muxer_settings: None,
+                split_max_time_sec: 0,
+                split_max_size_mb: 0,
+                split_manually: false,
+                filename_template: None,
                 name: name.into(),
             },
             context,
+            source_audio_tracks: Vec::new(),
         }
     }
 
@@ -358,6 +434,59 @@ impl SimpleOutputBuilder {
         self
     }
 
+    /// Starts a new file after the given number of seconds, 0 disables splitting by time.
+    /// The following files are named after the output path with the time they were started,
+    /// for example `recording 2024-01-31 12-00-00.mp4`.
+    pub fn split_max_time_sec(mut self, seconds: u32) -> Self {
+        self.settings.split_max_time_sec = seconds;
+        self
+    }
+
+    /// Starts a new file after the given size in megabytes, 0 disables splitting by size.
+    pub fn split_max_size_mb(mut self, megabytes: u32) -> Self {
+        self.settings.split_max_size_mb = megabytes;
+        self
+    }
+
+    /// Allows splitting the recording manually with `ObsOutputTrait::split_file`.
+    pub fn split_manually(mut self, enabled: bool) -> Self {
+        self.settings.split_manually = enabled;
+        self
+    }
+
+    /// Names the recordings using the given template. The output path is used as the directory
+    /// the recordings are saved to and every start of the output records to a new file,
+    /// which is also used for the files after a split.
+    pub fn filename_template(mut self, template: FilenameTemplate) -> Self {
+        self.settings.filename_template = Some(template);
+        self
+    }
+
+    /// Sets the audio tracks to record, each one as its own audio stream.
+    /// Only track 1 is recorded by default.
+    pub fn audio_tracks(mut self, tracks: ObsAudioTracks) -> Self {
+        self.settings.audio_tracks = tracks;
+        self
+    }
+
+    /// Sets the title of the audio stream of the given track (1-6).
+    pub fn audio_track_name<S: Into<String>>(mut self, track: usize, name: S) -> Self {
+        self.settings.audio_track_names.insert(track, name.into());
+        self
+    }
+
+    /// Sets the tracks the given source is mixed into when the output is built.
+    /// Sources are mixed into every track by default.
+    pub fn source_audio_tracks<S: ObsSourceTrait + Clone + 'static>(
+        mut self,
+        source: &S,
+        tracks: ObsAudioTracks,
+    ) -> Self {
+        self.source_audio_tracks
+            .push((Box::new(source.clone()), tracks));
+        self
+    }
+
     /// Builds and returns the configured output.
     pub fn build(mut self) -> Result<ObsOutputRef, ObsError> {
         // Determine the output type based on format
@@ -369,12 +498,29 @@ impl SimpleOutputBuilder {
 
         // Create output settings
         let mut output_settings = self.context.data()?;
-        output_settings.set_string("path", self.settings.path.clone().build())?;
+        match &self.settings.filename_template {
+            Some(template) => {
+                let template = template.with_output_tokens(
+                    &self.settings.name.to_string(),
+                    output_settings.runtime(),
+                )?;
+                let path = template.next_path(self.directory(), self.settings.format.extension());
+                output_settings
+                    .set_string("path", ObsPath::new(&path.to_string_lossy()).build())?;
+            }
+            None => {
+                output_settings.set_string("path", self.settings.path.clone().build())?;
+            }
+        }
 
         if let Some(ref muxer_settings) = self.settings.custom_muxer_settings {
             output_settings.set_string("muxer_settings", muxer_settings.as_str())?;
         }
 
+        if self.settings.is_splitting() {
+            self.configure_file_splitting(&mut output_settings)?;
+        }
+
         // Create the output
         let output_info = OutputInfo::new(
             output_id,
@@ -384,6 +530,9 @@ impl SimpleOutputBuilder {
         );
 
         let mut output = self.context.output(output_info)?;
+        if let Some(template) = &self.settings.filename_template {
+            output.set_start_hook(Some(self.filename_start_hook(template.clone())))?;
+        }
 
         // Create and configure video encoder (with hardware fallback)
         let video_encoder_type = self.select_video_encoder_type(&self.settings.video_encoder)?;
@@ -408,19 +557,39 @@ impl SimpleOutputBuilder {
         };
 
         log::trace!("Selected audio encoder: {:?}", audio_encoder_type);
-        let mut audio_settings = self.context.data()?;
-        audio_settings.set_string("rate_control", "CBR")?;
-        audio_settings.set_int("bitrate", self.settings.audio_bitrate as i64)?;
-
-        let audio_encoder_info = AudioEncoderInfo::new(
-            audio_encoder_type,
-            format!("{}_audio_encoder", self.settings.name),
-            Some(audio_settings),
-            None,
-        );
+        if self.settings.audio_tracks.is_empty() {
+            return Err(ObsError::InvalidOperation(
+                "At least one audio track must be recorded".to_string(),
+            ));
+        }
 
-        log::trace!("Creating audio encoder with info: {:?}", audio_encoder_info);
-        output.create_and_set_audio_encoder(audio_encoder_info, 0)?;
+        let audio_bitrate = self.settings.audio_bitrate;
+        let output_name = self.settings.name.clone();
+        let track_names = &self.settings.audio_track_names;
+        let context = &self.context;
+        output.create_and_set_audio_tracks(self.settings.audio_tracks, |mixer_idx| {
+            let track = mixer_idx + 1;
+            let mut audio_settings = context.data()?;
+            audio_settings.set_string("rate_control", "CBR")?;
+            audio_settings.set_int("bitrate", audio_bitrate as i64)?;
+
+            // The name of the encoder is used as the title of the audio stream
+            let name = match track_names.get(&track) {
+                Some(name) => name.clone(),
+                None if track == 1 => format!("{}_audio_encoder", output_name),
+                None => format!("{}_audio_encoder_{}", output_name, track),
+            };
+
+            let audio_encoder_info =
+                AudioEncoderInfo::new(audio_encoder_type.clone(), name, Some(audio_settings), None);
+
+            log::trace!("Creating audio encoder with info: {:?}", audio_encoder_info);
+            Ok(audio_encoder_info)
+        })?;
+
+        for (source, tracks) in self.source_audio_tracks.iter() {
+            source.set_audio_mixers(*tracks)?;
+        }
 
         Ok(output)
     }
@@ -489,6 +658,79 @@ impl SimpleOutputBuilder {
         }
     }
 
+    /// The directory recordings are saved to when a filename template is used
+    fn directory(&self) -> PathBuf {
+        self.settings.path.clone().into()
+    }
+
+    /// Creates a hook that generates a new path every time the output is started and updates
+    /// the tokens used for the names of split files.
+    fn filename_start_hook(&self, template: FilenameTemplate) -> ObsOutputStartHook {
+        let output_name = self.settings.name.to_string();
+        let directory = self.directory();
+        let extension = self.settings.format.extension();
+        let splitting = self.settings.is_splitting();
+
+        ObsOutputStartHook::new(move |settings| {
+            let template = template.with_output_tokens(&output_name, settings.runtime())?;
+            let path = template.next_path(&directory, extension);
+
+            settings.set_string("path", ObsPath::new(&path.to_string_lossy()).build())?;
+            if splitting {
+                settings.set_string("format", template.expand_tokens().as_str())?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn configure_file_splitting(&self, settings: &mut ObsData) -> Result<(), ObsError> {
+        let (directory, format, extension, allow_spaces) = match &self.settings.filename_template {
+            Some(template) => {
+                let template = template
+                    .with_output_tokens(&self.settings.name.to_string(), settings.runtime())?;
+                (
+                    self.directory().to_string_lossy().to_string(),
+                    template.expand_tokens(),
+                    self.settings.format.extension().to_string(),
+                    template.allow_spaces(),
+                )
+            }
+            None => {
+                let path: PathBuf = self.settings.path.clone().into();
+                let directory = path.parent().map(|p| p.to_string_lossy().to_string());
+                let stem = path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let extension = path
+                    .extension()
+                    .map(|e| e.to_string_lossy().to_string())
+                    .unwrap_or_default();
+
+                (
+                    directory.unwrap_or_default(),
+                    format!("{} %CCYY-%MM-%DD %hh-%mm-%ss", stem),
+                    extension,
+                    true,
+                )
+            }
+        };
+
+        settings.set_bool("split_file", true)?;
+        settings.set_int("max_time_sec", self.settings.split_max_time_sec as i64)?;
+        settings.set_int("max_size_mb", self.settings.split_max_size_mb as i64)?;
+
+        // The muxer names the following files using these settings
+        settings.set_string("directory", directory.as_str())?;
+        settings.set_string("format", format.as_str())?;
+        settings.set_string("extension", extension.as_str())?;
+        settings.set_bool("allow_spaces", allow_spaces)?;
+        settings.set_bool("allow_overwrite", false)?;
+
+        Ok(())
+    }
+
     fn configure_video_encoder(&self, settings: &mut ObsData) -> Result<(), ObsError> {
         // Configure rate control mode
         match self.settings.rate_control {
@@ -2,8 +2,14 @@ mod common;
 
 use libobs_wrapper::{
     context::ObsContext,
-    data::properties::ObsPropertyObject,
-    encoders::{ObsContextEncoders, ObsVideoEncoderType},
+    data::{
+        properties::{
+            types::ObsListItemValue, ObsProperty, ObsPropertyObject, ObsPropertyValue,
+            ObsValidationIssueKind,
+        },
+        ObsData, ObsDataSetters,
+    },
+    encoders::{ObsContextEncoders, ObsVideoEncoderBuilder, ObsVideoEncoderType},
     utils::StartupInfo,
 };
 
@@ -52,6 +58,167 @@ pub fn test_encoder_properties_access() {
     assert!(props.is_ok(), "Failed to get encoder properties");
 }
 
+/// Integration test: Test that the ordered property tree matches the flat property map
+#[test]
+pub fn test_encoder_properties_tree() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let context = ObsContext::new(StartupInfo::default()).unwrap();
+
+    let encoders = context.available_video_encoders().unwrap();
+
+    // Skip if no encoders available
+    if encoders.is_empty() {
+        eprintln!("Skipping test - no video encoders available");
+        return;
+    }
+
+    let encoder = encoders.into_iter().next().unwrap();
+
+    let props = encoder.get_properties().unwrap();
+    let tree = encoder.get_properties_tree().unwrap();
+
+    // Every top-level node of the tree should be present in the flat map
+    assert_eq!(tree.len(), props.len());
+    for node in tree.iter() {
+        assert!(props.contains_key(node.name()));
+        assert!(tree.find(node.name()).is_some());
+    }
+}
+
+/// Integration test: Test applying a list value through a properties session
+#[test]
+pub fn test_encoder_properties_session() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let context = ObsContext::new(StartupInfo::default()).unwrap();
+
+    let encoders = context.available_video_encoders().unwrap();
+
+    // Skip if no encoders available
+    if encoders.is_empty() {
+        eprintln!("Skipping test - no video encoders available");
+        return;
+    }
+
+    let encoder = encoders.into_iter().next().unwrap();
+    let settings = ObsData::new(context.runtime().clone()).unwrap();
+    let mut session = encoder.get_properties_session(settings).unwrap();
+
+    let tree = session.properties().unwrap();
+    let list = tree.flatten().into_iter().find_map(|node| {
+        let value = match node.list_items()?.first()?.value() {
+            ObsListItemValue::String(s) => ObsPropertyValue::String(s.clone()),
+            ObsListItemValue::Int(i) => ObsPropertyValue::Int(*i),
+            _ => return None,
+        };
+
+        Some((node.name().clone(), value))
+    });
+
+    let Some((name, value)) = list else {
+        eprintln!("Skipping test - encoder has no list properties");
+        return;
+    };
+
+    let updated = session.apply(name.as_str(), value).unwrap();
+    assert!(updated.find(&name).is_some());
+
+    // Applying a value to a property that doesn't exist should fail
+    assert!(session.apply("this_property_does_not_exist", true).is_err());
+}
+
+/// Integration test: Test exporting encoder properties as a JSON Schema document
+#[test]
+pub fn test_encoder_properties_json_schema() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let context = ObsContext::new(StartupInfo::default()).unwrap();
+
+    let encoders = context.available_video_encoders().unwrap();
+
+    // Skip if no encoders available
+    if encoders.is_empty() {
+        eprintln!("Skipping test - no video encoders available");
+        return;
+    }
+
+    let encoder = encoders.into_iter().next().unwrap();
+    let id = encoder.get_encoder_id().clone();
+
+    let schema =
+        ObsVideoEncoderBuilder::get_properties_json_schema_by_source_id(id, context.runtime())
+            .unwrap();
+
+    assert_eq!(schema["type"], "object");
+    let properties = schema["properties"].as_object().unwrap();
+
+    // Every property with a value should be part of the schema
+    let tree = encoder.get_properties_tree().unwrap();
+    for node in tree.flatten() {
+        if matches!(
+            node.property(),
+            ObsProperty::Button(_) | ObsProperty::Group(_)
+        ) {
+            continue;
+        }
+
+        assert!(properties.contains_key(node.name()));
+    }
+}
+
+/// Integration test: Test validating out-of-range settings against the encoder properties
+#[test]
+pub fn test_encoder_settings_validation() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let context = ObsContext::new(StartupInfo::default()).unwrap();
+
+    let encoders = context.available_video_encoders().unwrap();
+
+    // Skip if no encoders available
+    if encoders.is_empty() {
+        eprintln!("Skipping test - no video encoders available");
+        return;
+    }
+
+    let encoder = encoders.into_iter().next().unwrap();
+    let tree = encoder.get_properties_tree().unwrap();
+
+    // Hidden and disabled properties are not validated
+    let int_property = tree
+        .flatten()
+        .into_iter()
+        .filter(|node| *node.visible() && *node.enabled())
+        .find_map(|node| match node.property() {
+            ObsProperty::Int(p) if *p.max() < i32::MAX => Some((node.name().clone(), *p.max())),
+            _ => None,
+        });
+
+    let Some((name, max)) = int_property else {
+        eprintln!("Skipping test - encoder has no bounded int properties");
+        return;
+    };
+
+    let mut settings = ObsData::new(context.runtime().clone()).unwrap();
+    settings.set_int(name.as_str(), max as i64 + 1).unwrap();
+
+    let report = encoder.validate_settings(&settings).unwrap();
+    assert!(!report.is_valid());
+    assert!(report
+        .issues_for(&name)
+        .iter()
+        .any(|issue| matches!(issue.kind(), ObsValidationIssueKind::AboveMaximum { .. })));
+}
+
 /// Integration test: Test encoder type identification
 #[test]
 pub fn test_encoder_type_identification() {
@@ -1,6 +1,10 @@
 use libobs_wrapper::{
     context::ObsContext,
-    data::{ObsDataGetters, ObsDataSetters},
+    data::{
+        properties::{ObsProperty, ObsPropertyObject, ObsValidationIssueKind},
+        ObsDataGetters, ObsDataSetters,
+    },
+    sources::ObsSourceRef,
     utils::StartupInfo,
 };
 
@@ -56,3 +60,81 @@ pub fn test_data_get_nonexistent() {
     let nonexistent_double = data.get_double("nonexistent_key");
     assert_eq!(nonexistent_double, Ok(None));
 }
+
+/// Integration test: Color and font properties read their defaults from the defaults of the source
+#[test]
+pub fn test_color_and_font_property_defaults() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let context = ObsContext::new(StartupInfo::default()).unwrap();
+
+    let properties =
+        ObsSourceRef::get_properties_by_source_id("color_source", context.runtime()).unwrap();
+    let defaults =
+        ObsSourceRef::get_defaults_by_source_id("color_source", context.runtime()).unwrap();
+
+    let color = match properties.get("color") {
+        Some(ObsProperty::Color(property)) => property.get_default_color(&defaults).unwrap(),
+        Some(ObsProperty::ColorAlpha(property)) => property.get_default_color(&defaults).unwrap(),
+        other => panic!("Expected a color property, got {:?}", other),
+    };
+    assert!(color.is_some(), "The color source has no default color");
+
+    let source_id = if cfg!(target_os = "windows") {
+        "text_gdiplus"
+    } else {
+        "text_ft2_source"
+    };
+    let properties =
+        ObsSourceRef::get_properties_by_source_id(source_id, context.runtime()).unwrap();
+    let defaults = ObsSourceRef::get_defaults_by_source_id(source_id, context.runtime()).unwrap();
+
+    let Some(ObsProperty::Font(property)) = properties.get("font") else {
+        panic!("Expected a font property");
+    };
+    let font = property.get_default_font(&defaults).unwrap();
+    assert!(font.is_some_and(|font| *font.size() > 0));
+}
+
+/// Integration test: Validation uses the visibility the modified callbacks set for the validated settings
+#[test]
+pub fn test_validate_settings_toggles_visibility() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let context = ObsContext::new(StartupInfo::default()).unwrap();
+
+    // Reading from a file shows the file path and hides the text of the text source
+    let (source_id, from_file_key, file_key) = if cfg!(target_os = "windows") {
+        ("text_gdiplus", "read_from_file", "file")
+    } else {
+        ("text_ft2_source", "from_file", "text_file")
+    };
+    let missing_file = std::env::temp_dir().join("libobs_missing_text_file.txt");
+    let missing_file = missing_file.to_str().unwrap();
+
+    // The file path is hidden by default, so it is only checked once reading from a file is enabled
+    let mut settings = context.data().unwrap();
+    settings.set_bool(from_file_key, true).unwrap();
+    settings.set_string(file_key, missing_file).unwrap();
+
+    let report =
+        ObsSourceRef::validate_settings_by_source_id(source_id, &settings, context.runtime())
+            .unwrap();
+    assert!(report
+        .issues_for(file_key)
+        .iter()
+        .any(|issue| matches!(issue.kind(), ObsValidationIssueKind::PathNotFound { .. })));
+
+    settings.set_bool(from_file_key, false).unwrap();
+    let report =
+        ObsSourceRef::validate_settings_by_source_id(source_id, &settings, context.runtime())
+            .unwrap();
+    assert!(report.issues_for(file_key).is_empty());
+
+    // The validated settings are not changed by the modified callbacks
+    assert_eq!(settings.get_bool(from_file_key), Ok(Some(false)));
+}
@@ -1,9 +1,23 @@
 mod common;
 
+use std::time::Duration;
+
+use libobs_simple::output::{
+    filename::FilenameTemplate,
+    replay::ObsContextReplayExt,
+    simple::{ObsContextSimpleExt, OutputFormat},
+};
 use libobs_wrapper::{
     context::ObsContext,
-    data::ObsDataSetters,
-    utils::{ObsString, OutputInfo, StartupInfo},
+    data::{
+        object::ObsObjectTrait,
+        output::{ObsOutputTrait, ObsReconnectPolicy},
+        ObsDataGetters, ObsDataSetters,
+    },
+    enums::{ObsAudioTracks, ObsOutputDelayFlags, ObsOutputStopMode},
+    scenes::SceneItemExtSceneTrait,
+    sources::ObsSourceTrait,
+    utils::{AudioEncoderInfo, ObsError, ObsPath, ObsString, OutputInfo, SourceInfo, StartupInfo},
 };
 
 /// Integration test: Test output creation
@@ -108,3 +122,327 @@ pub fn test_output_different_settings() {
     let info2 = OutputInfo::new("ffmpeg_muxer", "configured_output", Some(settings2), None);
     assert!(context.output(info2).is_ok());
 }
+
+/// Integration test: Test collecting output statistics
+#[test]
+pub fn test_output_stats() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+
+    let mut settings = context.data().unwrap();
+    settings
+        .set_string("path", ObsString::new("stats_test.mp4"))
+        .unwrap();
+    let info = OutputInfo::new("ffmpeg_muxer", "stats_output", Some(settings), None);
+    let output = context.output(info).unwrap();
+
+    // The output was never started
+    assert_eq!(output.get_total_bytes().unwrap(), 0);
+    assert_eq!(output.get_frames_dropped().unwrap(), 0);
+    assert!(!output.is_reconnecting().unwrap());
+
+    let stats = output.get_stats().unwrap();
+    assert_eq!(*stats.total_bytes(), 0);
+    assert_eq!(*stats.bitrate_kbps(), None);
+
+    assert!(output.start_stats_sampler(Duration::ZERO).is_err());
+
+    let sampler = output
+        .start_stats_sampler(Duration::from_millis(20))
+        .unwrap();
+    let mut rx = sampler.subscribe();
+
+    let first = rx.blocking_recv().unwrap();
+    assert_eq!(*first.total_bytes(), 0);
+
+    // Bitrates are estimated from the second sample on
+    let second = rx.blocking_recv().unwrap();
+    assert_eq!(*second.bitrate_kbps(), Some(0.0));
+
+    drop(sampler);
+}
+
+/// Integration test: Test configuring the delay of an output
+#[test]
+pub fn test_output_delay() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+
+    let mut settings = context.data().unwrap();
+    settings
+        .set_string("path", ObsString::new("delay_test.mp4"))
+        .unwrap();
+    let info = OutputInfo::new("ffmpeg_muxer", "delay_output", Some(settings), None);
+    let mut output = context.output(info).unwrap();
+
+    assert_eq!(output.get_delay().unwrap(), 0);
+
+    output.set_delay(30, ObsOutputDelayFlags::PRESERVE).unwrap();
+    assert_eq!(output.get_delay().unwrap(), 30);
+
+    // The delay is only active while the output is running
+    assert_eq!(output.get_active_delay().unwrap(), 0);
+
+    assert!(output.stop_with_mode(ObsOutputStopMode::Force).is_err());
+
+    output.set_delay(0, ObsOutputDelayFlags::empty()).unwrap();
+    assert_eq!(output.get_delay().unwrap(), 0);
+}
+
+/// Integration test: Test reconnect settings and the reconnect policy of an output
+#[test]
+pub fn test_output_reconnect() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+
+    let mut settings = context.data().unwrap();
+    settings
+        .set_string("path", ObsString::new("reconnect_test.mp4"))
+        .unwrap();
+    let info = OutputInfo::new("ffmpeg_muxer", "reconnect_output", Some(settings), None);
+    let output = context.output(info).unwrap();
+
+    output.set_reconnect_settings(5, 2).unwrap();
+    output.set_reconnect_settings(0, 0).unwrap();
+
+    let policy = ObsReconnectPolicy::new()
+        .set_max_attempts(3)
+        .set_initial_delay(Duration::from_millis(100));
+    let watcher = output.start_reconnect_policy(policy).unwrap();
+    let mut events = watcher.subscribe();
+
+    // The output was never started, so nothing should be restarted
+    std::thread::sleep(Duration::from_millis(300));
+    assert!(events.try_recv().is_err());
+    assert!(!output.is_active().unwrap());
+
+    drop(watcher);
+}
+
+/// Integration test: Test recording multiple audio tracks with selected sources
+#[test]
+pub fn test_output_audio_tracks() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+
+    let mut settings = context.data().unwrap();
+    settings
+        .set_string("path", ObsString::new("tracks_test.mkv"))
+        .unwrap();
+    let info = OutputInfo::new("ffmpeg_muxer", "tracks_output", Some(settings), None);
+    let mut output = context.output(info).unwrap();
+
+    let encoders = output
+        .create_and_set_audio_tracks(
+            ObsAudioTracks::TRACK_1 | ObsAudioTracks::TRACK_3,
+            |mixer_idx| {
+                Ok(AudioEncoderInfo::new(
+                    "ffmpeg_aac",
+                    format!("Track {}", mixer_idx + 1),
+                    None,
+                    None,
+                ))
+            },
+        )
+        .unwrap();
+    assert_eq!(encoders.len(), 2);
+    assert_eq!(output.audio_encoders().read().unwrap().len(), 2);
+
+    // Fewer tracks detach the encoders of the previous ones
+    output
+        .create_and_set_audio_tracks(ObsAudioTracks::TRACK_2, |_| {
+            Ok(AudioEncoderInfo::new(
+                "ffmpeg_aac",
+                "Commentary",
+                None,
+                None,
+            ))
+        })
+        .unwrap();
+    assert_eq!(output.audio_encoders().read().unwrap().len(), 1);
+
+    let mut scene = context.scene("tracks_scene", Some(0)).unwrap();
+    let item = scene
+        .add_and_create_source(SourceInfo::new("color_source", "tracks_color", None, None))
+        .unwrap();
+    let source = item.inner_source();
+
+    let tracks = ObsAudioTracks::TRACK_2 | ObsAudioTracks::TRACK_4;
+    source.set_audio_mixers(tracks).unwrap();
+    assert_eq!(source.get_audio_mixers().unwrap(), tracks);
+
+    let output = context
+        .simple_output_builder("simple_tracks_output", ObsPath::new("simple_tracks.mkv"))
+        .audio_tracks(ObsAudioTracks::TRACK_1 | ObsAudioTracks::TRACK_2)
+        .audio_track_name(1, "Game")
+        .audio_track_name(2, "Microphone")
+        .source_audio_tracks(source, ObsAudioTracks::TRACK_1)
+        .build()
+        .unwrap();
+
+    let names = output
+        .audio_encoders()
+        .read()
+        .unwrap()
+        .values()
+        .map(|e| e.name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(names.len(), 2);
+    assert!(names.contains(&"Game".to_string()));
+    assert!(names.contains(&"Microphone".to_string()));
+    assert_eq!(source.get_audio_mixers().unwrap(), ObsAudioTracks::TRACK_1);
+}
+
+/// Integration test: Test file splitting of recording outputs
+#[test]
+pub fn test_output_file_splitting() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+
+    let output = context
+        .simple_output_builder("split_output", ObsPath::new("split_test.mkv"))
+        .split_max_time_sec(3600)
+        .split_max_size_mb(2048)
+        .split_manually(true)
+        .build()
+        .unwrap();
+
+    let settings = output.settings().unwrap();
+    assert_eq!(settings.get_bool("split_file").unwrap(), Some(true));
+    assert_eq!(settings.get_int("max_time_sec").unwrap(), Some(3600));
+    assert_eq!(settings.get_int("max_size_mb").unwrap(), Some(2048));
+
+    assert!(output.on_file_changed().is_ok());
+
+    // The muxer only enables splitting once it is started
+    assert!(output.split_file().is_err());
+
+    // Streaming outputs can't split their recording
+    let info = OutputInfo::new("rtmp_output", "split_rtmp_output", None, None);
+    let rtmp = context.output(info).unwrap();
+    assert!(rtmp.on_file_changed().is_err());
+    assert!(rtmp.split_file().is_err());
+}
+
+/// Integration test: Test chapter markers of hybrid MP4 outputs
+#[test]
+pub fn test_output_add_chapter() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+
+    let mut settings = context.data().unwrap();
+    settings
+        .set_string("path", ObsString::new("chapter_test.mp4"))
+        .unwrap();
+    let info = OutputInfo::new("mp4_output", "chapter_output", Some(settings), None);
+    let output = context.output(info).unwrap();
+
+    // Chapters are part of the recording, so the output has to be active
+    let res = output.add_chapter("Intro");
+    assert!(matches!(res, Err(ObsError::InvalidOperation(_))));
+
+    let mut settings = context.data().unwrap();
+    settings
+        .set_string("path", ObsString::new("chapter_test.mkv"))
+        .unwrap();
+    let info = OutputInfo::new("ffmpeg_muxer", "chapter_muxer_output", Some(settings), None);
+    let output = context.output(info).unwrap();
+
+    // Other formats are rejected whether or not the output is active
+    let res = output.add_chapter("Intro");
+    assert!(
+        matches!(&res, Err(ObsError::InvalidOperation(e)) if e.contains("doesn't support chapter markers")),
+        "Expected an unsupported format error, got {:?}",
+        res
+    );
+}
+
+/// Integration test: Test filename templates of recordings and replays
+#[test]
+pub fn test_output_filename_template() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let context = ObsContext::new(StartupInfo::default()).unwrap();
+
+    let output = context
+        .simple_output_builder("template_output", ObsPath::new("recordings"))
+        .format(OutputFormat::MatroskaVideo)
+        .filename_template(FilenameTemplate::new("{output} {scene}%CCYY"))
+        .split_manually(true)
+        .build()
+        .unwrap();
+
+    let settings = output.settings().unwrap();
+    let path = settings.get_string("path").unwrap().unwrap();
+    assert!(path.starts_with("recordings/template_output "));
+    assert!(path.ends_with(".mkv"));
+    assert_eq!(
+        settings.get_string("format").unwrap().as_deref(),
+        Some("template_output %CCYY")
+    );
+    assert_eq!(
+        settings.get_string("extension").unwrap().as_deref(),
+        Some("mkv")
+    );
+
+    let replay = context
+        .replay_buffer_builder("template_replay", ObsPath::new("replays"))
+        .filename_template(FilenameTemplate::new("{output}_%hh").set_allow_spaces(false))
+        .build()
+        .unwrap();
+
+    let settings = replay.settings().unwrap();
+    assert_eq!(
+        settings.get_string("format").unwrap().as_deref(),
+        Some("template_replay_%hh")
+    );
+    assert_eq!(settings.get_bool("allow_spaces").unwrap(), Some(false));
+}
+
+/// Integration test: Test sending closed captions to outputs
+#[test]
+pub fn test_output_captions() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let context = ObsContext::new(StartupInfo::default()).unwrap();
+
+    let output = context
+        .simple_output_builder("caption_output", ObsPath::new("caption_test.mp4"))
+        .build()
+        .unwrap();
+
+    // OBS drops captions of inactive outputs
+    let res = output.send_caption_text("Hello", Duration::from_secs(2));
+    assert!(matches!(res, Err(ObsError::InvalidOperation(_))));
+
+    let captions = output.start_caption_queue().unwrap();
+    assert_eq!(captions.output_time().unwrap(), None);
+
+    // Captions wait for the output to be started
+    captions.push("Hello", Duration::from_secs(2)).unwrap();
+    captions
+        .push_at("World", Duration::from_secs(1), Duration::from_secs(2))
+        .unwrap();
+}
@@ -1,6 +1,26 @@
 mod common;
 
-use libobs_wrapper::{context::ObsContext, utils::StartupInfo};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use libobs_wrapper::{
+    context::ObsContext,
+    data::{object::ObsObjectTrait, ObsDataSetters},
+    enums::{
+        ObsBlendingMethod, ObsBlendingMode, ObsBoundsType, ObsOrderMovement, ObsScaleType,
+        ObsSceneDuplicateType,
+    },
+    graphics::{Rect, Vec2},
+    scenes::{
+        ObsAnimationStatus, ObsEasing, ObsLayout, ObsLayoutKind, ObsSceneItemAnimation,
+        ObsSceneItemCrop, ObsTransformInfoBuilder, ObsTransformKeyframe, SceneItemExtSceneTrait,
+        SceneItemTrait,
+    },
+    sources::{ObsSceneItemTransition, ObsSourceTrait, ObsTransitionType},
+    utils::{SourceInfo, StartupInfo},
+};
 
 /// Integration test: Test creating a scene
 #[test]
@@ -90,3 +110,572 @@ pub fn test_scene_different_channels() {
     let scene1 = context.scene("channel_1_scene", Some(1)).unwrap();
     assert!(scene1.set_to_channel(1).is_ok());
 }
+
+/// Integration test: Test crop, visibility, lock, blending and scale filter of a scene item
+#[test]
+pub fn test_scene_item_properties() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+    let mut scene = context.scene("item_properties_scene", Some(0)).unwrap();
+
+    let item = scene
+        .add_and_create_source(SourceInfo::new("color_source", "color", None, None))
+        .unwrap();
+
+    let crop = ObsSceneItemCrop::new(10, 20, 30, 40);
+    item.set_crop(crop).unwrap();
+    assert_eq!(item.get_crop().unwrap(), crop);
+
+    item.set_visible(false).unwrap();
+    assert!(!item.is_visible().unwrap());
+
+    item.set_locked(true).unwrap();
+    assert!(item.is_locked().unwrap());
+
+    item.set_blending_mode(ObsBlendingMode::Multiply).unwrap();
+    assert_eq!(item.get_blending_mode().unwrap(), ObsBlendingMode::Multiply);
+
+    item.set_blending_method(ObsBlendingMethod::SrgbOff)
+        .unwrap();
+    assert_eq!(
+        item.get_blending_method().unwrap(),
+        ObsBlendingMethod::SrgbOff
+    );
+
+    item.set_scale_filter(ObsScaleType::Lanczos).unwrap();
+    assert_eq!(item.get_scale_filter().unwrap(), ObsScaleType::Lanczos);
+
+    assert!(item.get_id().unwrap() > 0);
+}
+
+/// Integration test: Test reordering scene items
+#[test]
+pub fn test_scene_item_order() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+    let mut scene = context.scene("item_order_scene", Some(0)).unwrap();
+
+    let bottom = scene
+        .add_and_create_source(SourceInfo::new("color_source", "bottom", None, None))
+        .unwrap();
+    let top = scene
+        .add_and_create_source(SourceInfo::new("color_source", "top", None, None))
+        .unwrap();
+
+    assert_eq!(bottom.get_order_position().unwrap(), 0);
+    assert_eq!(top.get_order_position().unwrap(), 1);
+
+    bottom.set_order(ObsOrderMovement::MoveTop).unwrap();
+    assert_eq!(bottom.get_order_position().unwrap(), 1);
+    assert_eq!(top.get_order_position().unwrap(), 0);
+
+    let ordered = scene.get_ordered_scene_items().unwrap();
+    assert_eq!(ordered.len(), 2);
+    assert_eq!(ordered[0].as_ptr().get_ptr(), top.as_ptr().get_ptr());
+    assert_eq!(ordered[1].as_ptr().get_ptr(), bottom.as_ptr().get_ptr());
+
+    bottom.set_order_position(0).unwrap();
+    assert_eq!(bottom.get_order_position().unwrap(), 0);
+}
+
+/// Integration test: Test enumerating the items of a scene from OBS
+#[test]
+pub fn test_enum_scene_items() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+    let mut scene = context.scene("enum_items_scene", Some(0)).unwrap();
+
+    scene
+        .add_and_create_source(SourceInfo::new("color_source", "first", None, None))
+        .unwrap();
+    scene
+        .add_and_create_source(SourceInfo::new("color_source", "second", None, None))
+        .unwrap();
+
+    let items = scene.enum_scene_items().unwrap();
+    let names: Vec<String> = items
+        .iter()
+        .map(|item| item.inner_source().name().to_string())
+        .collect();
+    assert_eq!(names, vec!["first", "second"]);
+    assert_eq!(items[0].inner_source().id().to_string(), "color_source");
+
+    // Dropping the enumerated items must keep them in the scene
+    drop(items);
+    assert_eq!(scene.enum_scene_items().unwrap().len(), 2);
+}
+
+/// Integration test: Test that enumerating scene items keeps the signals of their sources
+#[test]
+pub fn test_enum_scene_items_keeps_signals() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+    let mut scene = context.scene("enum_signals_scene", Some(0)).unwrap();
+
+    let item = scene
+        .add_and_create_source(SourceInfo::new("color_source", "signal_source", None, None))
+        .unwrap();
+    let source = item.inner_source().clone();
+    let mut updates = source.signals().on_update().unwrap();
+
+    // The enumerated items wrap the same source and are dropped right away
+    drop(scene.enum_scene_items().unwrap());
+
+    let mut settings = context.data().unwrap();
+    settings.set_int("width", 200).unwrap();
+    source.update_settings(settings).unwrap();
+
+    // Video sources apply their settings and emit the signal on the next video tick
+    let start = Instant::now();
+    while updates.try_recv().is_err() {
+        assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "The update signal of the source didn't arrive"
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert!(source.signals().on_update().is_ok());
+}
+
+/// Integration test: Signal managers of the same source share their senders
+#[test]
+pub fn test_shared_signal_managers() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+    let mut scene = context.scene("shared_signals_scene", Some(0)).unwrap();
+
+    scene
+        .add_and_create_source(SourceInfo::new("color_source", "shared_source", None, None))
+        .unwrap();
+
+    // Every enumeration wraps the source again, which creates another signal manager for it
+    let first = scene.enum_scene_items().unwrap();
+    let second = scene.enum_scene_items().unwrap();
+    let first_source = first[0].inner_source().clone();
+    let second_source = second[0].inner_source().clone();
+    assert!(!Arc::ptr_eq(
+        first_source.signals(),
+        second_source.signals()
+    ));
+
+    let mut updates = second_source.signals().on_update().unwrap();
+    drop(first_source);
+    drop(first);
+
+    let mut settings = context.data().unwrap();
+    settings.set_int("width", 300).unwrap();
+    second_source.update_settings(settings).unwrap();
+
+    let start = Instant::now();
+    while updates.try_recv().is_err() {
+        assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "The update signal of the remaining manager didn't arrive"
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// Integration test: Test grouping scene items
+#[test]
+pub fn test_scene_groups() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+    let mut scene = context.scene("groups_scene", Some(0)).unwrap();
+
+    let item = scene
+        .add_and_create_source(SourceInfo::new("color_source", "grouped", None, None))
+        .unwrap();
+    item.set_source_position(Vec2::new(200.0, 100.0)).unwrap();
+
+    let group = scene.add_group("group").unwrap();
+    assert!(group.is_group().unwrap());
+    assert!(!item.is_group().unwrap());
+    assert!(scene.get_group("group").unwrap().is_some());
+    assert!(scene.get_group("missing").unwrap().is_none());
+
+    group.add_item(&item).unwrap();
+    let items = group.enum_items().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].as_ptr().get_ptr(), item.as_ptr().get_ptr());
+
+    // The item keeps its position in the scene
+    let local = item.get_source_position().unwrap();
+    let position = group.local_to_scene(local).unwrap();
+    assert!((position.x() - 200.0).abs() < 0.01);
+    assert!((position.y() - 100.0).abs() < 0.01);
+
+    let back = group.scene_to_local(position).unwrap();
+    assert!((back.x() - local.x()).abs() < 0.01);
+    assert!((back.y() - local.y()).abs() < 0.01);
+
+    group.remove_item(&item).unwrap();
+    assert!(group.enum_items().unwrap().is_empty());
+
+    group.add_item(&item).unwrap();
+    scene.ungroup(group).unwrap();
+    assert!(scene.get_group("group").unwrap().is_none());
+    assert_eq!(scene.enum_scene_items().unwrap().len(), 1);
+}
+
+/// Integration test: Test adding scenes to other scenes
+#[test]
+pub fn test_nested_scenes() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+    let mut outer = context.scene("outer_scene", Some(0)).unwrap();
+    let mut inner = context.scene("inner_scene", None).unwrap();
+
+    inner
+        .add_and_create_source(SourceInfo::new("color_source", "inner_color", None, None))
+        .unwrap();
+
+    let item = outer.add_source(inner.clone()).unwrap();
+    assert_eq!(item.inner_source().name().to_string(), "inner_scene");
+
+    // Nesting the outer scene in the inner scene or a scene in itself must fail
+    assert!(inner.add_source(outer.clone()).is_err());
+    assert!(outer.add_source(outer.clone()).is_err());
+
+    // The outer scene keeps the inner scene alive
+    drop(inner);
+    drop(item);
+    let items = outer.enum_scene_items().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].inner_source().id().to_string(), "scene");
+}
+
+/// Integration test: Test duplicating a scene
+#[test]
+pub fn test_scene_duplicate() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+    let mut scene = context.scene("template_scene", None).unwrap();
+
+    let original = scene
+        .add_and_create_source(SourceInfo::new(
+            "color_source",
+            "template_color",
+            None,
+            None,
+        ))
+        .unwrap();
+
+    let refs = scene
+        .duplicate("template_refs", ObsSceneDuplicateType::Refs)
+        .unwrap();
+    let copy = scene
+        .duplicate("template_copy", ObsSceneDuplicateType::Copy)
+        .unwrap();
+
+    assert_eq!(refs.name().to_string(), "template_refs");
+
+    // Referenced duplicates share the source, copies get their own one
+    let refs_items = refs.get_ordered_scene_items().unwrap();
+    assert_eq!(refs_items.len(), 1);
+    assert_eq!(
+        refs_items[0].inner_source_dyn().as_ptr().get_ptr(),
+        original.inner_source().as_ptr().get_ptr()
+    );
+
+    let copy_items = copy.get_ordered_scene_items().unwrap();
+    assert_eq!(copy_items.len(), 1);
+    assert_ne!(
+        copy_items[0].inner_source_dyn().as_ptr().get_ptr(),
+        original.inner_source().as_ptr().get_ptr()
+    );
+}
+
+/// Integration test: Test show and hide transitions of a scene item
+#[test]
+pub fn test_scene_item_transitions() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+    let mut scene = context.scene("transitions_scene", Some(0)).unwrap();
+
+    let item = scene
+        .add_and_create_source(SourceInfo::new("color_source", "overlay", None, None))
+        .unwrap();
+
+    assert_eq!(
+        item.get_transition(ObsSceneItemTransition::Show).unwrap(),
+        None
+    );
+
+    item.set_transition(
+        ObsSceneItemTransition::Show,
+        Some(ObsTransitionType::Fade),
+        None,
+    )
+    .unwrap();
+    item.set_transition_duration(ObsSceneItemTransition::Show, 500)
+        .unwrap();
+
+    assert_eq!(
+        item.get_transition(ObsSceneItemTransition::Show).unwrap(),
+        Some(ObsTransitionType::Fade)
+    );
+    assert_eq!(
+        item.get_transition_duration(ObsSceneItemTransition::Show)
+            .unwrap(),
+        500
+    );
+    assert_eq!(
+        item.get_transition(ObsSceneItemTransition::Hide).unwrap(),
+        None
+    );
+
+    item.set_visible(false).unwrap();
+    item.set_visible(true).unwrap();
+
+    item.set_transition(ObsSceneItemTransition::Show, None, None)
+        .unwrap();
+    assert_eq!(
+        item.get_transition(ObsSceneItemTransition::Show).unwrap(),
+        None
+    );
+}
+
+/// Integration test: Test saving a scene collection and loading it into a new context
+#[test]
+pub fn test_scene_collection_save_load() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let path = std::env::temp_dir().join("libobs_test_scene_collection.json");
+
+    {
+        let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+        let mut scene = context.scene("collection_main", Some(0)).unwrap();
+        let mut other = context.scene("collection_other", None).unwrap();
+
+        let item = scene
+            .add_and_create_source(SourceInfo::new("color_source", "background", None, None))
+            .unwrap();
+        item.set_source_position(Vec2::new(10.0, 20.0)).unwrap();
+        scene
+            .add_and_create_source(SourceInfo::new("color_source", "overlay", None, None))
+            .unwrap();
+        other
+            .add_and_create_source(SourceInfo::new("color_source", "other_overlay", None, None))
+            .unwrap();
+
+        context
+            .save_scene_collection("Test Collection", &path)
+            .unwrap();
+    }
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+    let collection = context.load_scene_collection(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(collection.name(), "Test Collection");
+    assert_eq!(collection.scenes().len(), 2);
+    assert_eq!(collection.scenes()[0].name().to_string(), "collection_main");
+    assert_eq!(
+        collection
+            .current_scene()
+            .as_ref()
+            .map(|s| s.name().to_string()),
+        Some("collection_main".to_string())
+    );
+
+    let main = context.get_scene("collection_main").unwrap().unwrap();
+    let items = main.get_ordered_scene_items().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].inner_source_dyn().name().to_string(), "background");
+    let position = items[0].get_source_position().unwrap();
+    assert_eq!((*position.x(), *position.y()), (10.0, 20.0));
+    assert_eq!(items[1].inner_source_dyn().name().to_string(), "overlay");
+
+    let other = context.get_scene("collection_other").unwrap().unwrap();
+    assert_eq!(other.get_ordered_scene_items().unwrap().len(), 1);
+}
+
+/// Integration test: Test animating the transform of a scene item with keyframes
+#[test]
+pub fn test_scene_item_animation() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+    let mut scene = context.scene("animation_scene", Some(0)).unwrap();
+
+    let item = scene
+        .add_and_create_source(SourceInfo::new("color_source", "lower_third", None, None))
+        .unwrap();
+    item.set_source_position(Vec2::new(0.0, 0.0)).unwrap();
+
+    let animation = ObsSceneItemAnimation::new().add_keyframe(
+        ObsTransformKeyframe::new(Duration::from_millis(200))
+            .set_pos(Vec2::new(100.0, 50.0))
+            .set_crop(ObsSceneItemCrop::new(10, 0, 0, 0))
+            .set_easing(ObsEasing::EaseOutCubic),
+    );
+
+    let handle = item.animate(&animation).unwrap();
+    assert_eq!(handle.status(), ObsAnimationStatus::Running);
+
+    let start = Instant::now();
+    while !handle.is_finished() && start.elapsed() < Duration::from_secs(5) {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    assert_eq!(handle.status(), ObsAnimationStatus::Finished);
+    let position = item.get_source_position().unwrap();
+    assert_eq!((*position.x(), *position.y()), (100.0, 50.0));
+    assert_eq!(item.get_crop().unwrap(), ObsSceneItemCrop::new(10, 0, 0, 0));
+
+    // Cancelling leaves the item where it currently is
+    let handle = item
+        .animate(&ObsSceneItemAnimation::new().add_keyframe(
+            ObsTransformKeyframe::new(Duration::from_secs(60)).set_pos(Vec2::new(0.0, 0.0)),
+        ))
+        .unwrap();
+    handle.cancel();
+    assert_eq!(handle.status(), ObsAnimationStatus::Cancelled);
+}
+
+/// Integration test: Test arranging scene items with a layout
+#[test]
+pub fn test_scene_layout() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let startup_info = StartupInfo::default();
+    let base_width = startup_info.get_video_info().get_base_width();
+    let base_height = startup_info.get_video_info().get_base_height();
+
+    let mut context = ObsContext::new(startup_info).unwrap();
+    let mut scene = context.scene("layout_scene", Some(0)).unwrap();
+
+    let mut items = Vec::new();
+    for i in 0..4 {
+        items.push(
+            scene
+                .add_and_create_source(SourceInfo::new(
+                    "color_source",
+                    format!("camera_{}", i),
+                    None,
+                    None,
+                ))
+                .unwrap(),
+        );
+    }
+
+    let layout = ObsLayout::new(ObsLayoutKind::Grid { columns: None }).set_padding(10.0);
+    let item_refs = items
+        .iter()
+        .map(|i| i as &dyn SceneItemTrait)
+        .collect::<Vec<_>>();
+    scene.apply_layout(&layout, &item_refs).unwrap();
+
+    let cells = layout
+        .compute_cells(base_width, base_height, items.len())
+        .unwrap();
+    for (item, cell) in items.iter().zip(cells) {
+        let info = item.get_transform_info().unwrap();
+        let (pos, bounds) = (info.get_pos(), info.get_bounds());
+
+        assert_eq!(
+            (*pos.x(), *pos.y()),
+            (cell.x + cell.width / 2.0, cell.y + cell.height / 2.0)
+        );
+        assert_eq!((*bounds.x(), *bounds.y()), (cell.width, cell.height));
+    }
+
+    // Items of other scenes are rejected
+    let mut other = context.scene("layout_other", None).unwrap();
+    let foreign = other
+        .add_and_create_source(SourceInfo::new("color_source", "foreign", None, None))
+        .unwrap();
+    assert!(scene.apply_layout(&layout, &[&items[0], &foreign]).is_err());
+}
+
+/// Integration test: Test finding the topmost scene item at a point
+#[test]
+pub fn test_scene_item_at() {
+    let _ = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
+        .is_test(true)
+        .try_init();
+
+    let mut context = ObsContext::new(StartupInfo::default()).unwrap();
+    let mut scene = context.scene("hit_test_scene", Some(0)).unwrap();
+
+    let bottom = scene
+        .add_and_create_source(SourceInfo::new("color_source", "bottom", None, None))
+        .unwrap();
+    let top = scene
+        .add_and_create_source(SourceInfo::new("color_source", "top", None, None))
+        .unwrap();
+
+    for (item, pos) in [(&bottom, 0.0), (&top, 100.0)] {
+        let info = ObsTransformInfoBuilder::new()
+            .set_pos(Vec2::new(pos, pos))
+            .set_bounds(Vec2::new(200.0, 200.0))
+            .set_bounds_type(ObsBoundsType::Stretch)
+            .build_with_fallback(item)
+            .unwrap();
+        item.set_transform_info(&info).unwrap();
+    }
+
+    let rect = top.get_oriented_box().unwrap().bounding_rect();
+    assert_eq!(rect, Rect::new(100.0, 100.0, 200.0, 200.0));
+
+    let name_at = |point: Vec2| {
+        scene
+            .item_at(point)
+            .unwrap()
+            .map(|item| item.inner_source().name().to_string())
+    };
+
+    assert_eq!(name_at(Vec2::new(50.0, 50.0)), Some("bottom".to_string()));
+    assert_eq!(name_at(Vec2::new(150.0, 150.0)), Some("top".to_string()));
+    assert_eq!(name_at(Vec2::new(250.0, 250.0)), Some("top".to_string()));
+    assert_eq!(name_at(Vec2::new(350.0, 350.0)), None);
+
+    // Hidden items are skipped
+    top.set_visible(false).unwrap();
+    assert_eq!(name_at(Vec2::new(150.0, 150.0)), Some("bottom".to_string()));
+
+    // The box is rotated around the top left corner of the item
+    top.set_visible(true).unwrap();
+    let info = ObsTransformInfoBuilder::new()
+        .set_rot(45.0)
+        .build_with_fallback(&top)
+        .unwrap();
+    top.set_transform_info(&info).unwrap();
+
+    assert_eq!(name_at(Vec2::new(100.0, 150.0)), Some("top".to_string()));
+    assert_eq!(name_at(Vec2::new(250.0, 150.0)), None);
+}
@@ -0,0 +1,273 @@
+//! Closed captions for outputs.
+//!
+//! Caption text sent with `ObsOutputTrait::send_caption_text` is inserted by OBS as CEA-608
+//! captions into the video of the output, which works for H.264, HEVC and AV1 encoders.
+//! OBS inserts the text into every video track of the output, so there is no track to select.
+//!
+//! OBS shows a caption with the next encoded frame. Captions that belong to a certain point of
+//! the output (for example the result of a speech to text service) can be queued with an
+//! `ObsCaptionQueue`, which sends them once the output has been active for their timestamp.
+
+use std::time::Duration;
+
+#[cfg(feature = "enable_runtime")]
+use std::{
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc, RwLock,
+    },
+    thread::JoinHandle,
+    time::Instant,
+};
+
+#[cfg(feature = "enable_runtime")]
+use crate::utils::ObsError;
+
+#[cfg(feature = "enable_runtime")]
+use super::{reconnect::try_recv_signal, ObsOutputTrait};
+
+/// How often the caption thread checks whether queued captions are due
+#[cfg(feature = "enable_runtime")]
+const CAPTION_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "enable_runtime"), allow(dead_code))]
+struct QueuedCaption {
+    text: String,
+    display_duration: Duration,
+    /// The time since the start of the output the caption belongs to, `None` to send it right away
+    timestamp: Option<Duration>,
+}
+
+/// Captions waiting to be sent, ordered by their timestamp.
+#[derive(Debug, Default)]
+#[cfg_attr(not(feature = "enable_runtime"), allow(dead_code))]
+struct PendingCaptions {
+    captions: Vec<QueuedCaption>,
+}
+
+#[cfg_attr(not(feature = "enable_runtime"), allow(dead_code))]
+impl PendingCaptions {
+    fn push(&mut self, caption: QueuedCaption) {
+        // Captions with the same timestamp keep the order they were queued in
+        let index = self
+            .captions
+            .partition_point(|queued| queued.timestamp <= caption.timestamp);
+        self.captions.insert(index, caption);
+    }
+
+    /// Removes and returns the captions that are due after the output has been active
+    /// for `elapsed`. Captions that would already be gone from the screen are dropped.
+    fn take_due(&mut self, elapsed: Duration) -> Vec<QueuedCaption> {
+        let count = self
+            .captions
+            .partition_point(|queued| queued.timestamp.is_none_or(|t| t <= elapsed));
+
+        self.captions
+            .drain(..count)
+            .filter(|caption| match caption.timestamp {
+                Some(timestamp) if timestamp + caption.display_duration <= elapsed => {
+                    log::debug!("Dropping outdated caption {:?}", caption.text);
+                    false
+                }
+                _ => true,
+            })
+            .collect()
+    }
+
+    fn clear(&mut self) {
+        self.captions.clear();
+    }
+}
+
+/// Sends captions to an output at the time they belong to, on a background thread.
+/// The queue keeps the output alive and is stopped when it is dropped.
+///
+/// Timestamps are measured from the moment the output started. Captions that are queued
+/// while the output is inactive are sent after it started, pending captions are discarded
+/// when it stops.
+///
+/// ```ignore
+/// let captions = output.start_caption_queue()?;
+/// captions.push("Hello and welcome", Duration::from_secs(3))?;
+///
+/// // A caption for something that was said 5 seconds after the output started
+/// captions.push_at("to the stream", Duration::from_secs(5), Duration::from_secs(2))?;
+/// ```
+#[cfg(feature = "enable_runtime")]
+#[derive(Debug)]
+pub struct ObsCaptionQueue {
+    /// Dropping this sender stops the thread
+    sender: Option<mpsc::Sender<QueuedCaption>>,
+    started_at: Arc<RwLock<Option<Instant>>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+#[cfg(feature = "enable_runtime")]
+impl ObsCaptionQueue {
+    pub(crate) fn new<O>(output: O) -> Result<Self, ObsError>
+    where
+        O: ObsOutputTrait + Clone + 'static,
+    {
+        let mut start_signals = output.signals().on_start()?;
+        let mut stop_signals = output.signals().on_stop()?;
+
+        // The exact start time of an output that is already active is unknown
+        let started_at = Arc::new(RwLock::new(output.is_active()?.then(Instant::now)));
+        let (sender, receiver) = mpsc::channel::<QueuedCaption>();
+
+        let thread_started_at = started_at.clone();
+        let thread = std::thread::Builder::new()
+            .name("obs-output-captions".to_string())
+            .spawn(move || {
+                let mut pending = PendingCaptions::default();
+
+                loop {
+                    match receiver.recv_timeout(CAPTION_POLL_INTERVAL) {
+                        Ok(caption) => pending.push(caption),
+                        Err(RecvTimeoutError::Timeout) => {}
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+
+                    let Ok(mut started_at) = thread_started_at.write() else {
+                        log::warn!("The start time of the output is poisoned");
+                        break;
+                    };
+
+                    // Stops are handled first, so a restart within one poll keeps the output active
+                    while try_recv_signal(&mut stop_signals).is_some() {
+                        *started_at = None;
+                        pending.clear();
+                    }
+                    while try_recv_signal(&mut start_signals).is_some() {
+                        *started_at = Some(Instant::now());
+                    }
+
+                    let Some(elapsed) = started_at.map(|started_at| started_at.elapsed()) else {
+                        continue;
+                    };
+                    drop(started_at);
+
+                    for caption in pending.take_due(elapsed) {
+                        if let Err(e) =
+                            output.send_caption_text(&caption.text, caption.display_duration)
+                        {
+                            log::warn!("Failed to send caption: {:?}", e);
+                        }
+                    }
+                }
+            })
+            .map_err(|e| ObsError::IoError(e.to_string()))?;
+
+        Ok(Self {
+            sender: Some(sender),
+            started_at,
+            thread: Some(thread),
+        })
+    }
+
+    /// Queues a caption that is sent right away, or as soon as the output is started.
+    pub fn push<S: Into<String>>(
+        &self,
+        text: S,
+        display_duration: Duration,
+    ) -> Result<(), ObsError> {
+        self.queue(QueuedCaption {
+            text: text.into(),
+            display_duration,
+            timestamp: None,
+        })
+    }
+
+    /// Queues a caption that is sent once the output has been active for `timestamp`.
+    /// Captions that would already be gone from the screen by then are dropped.
+    pub fn push_at<S: Into<String>>(
+        &self,
+        text: S,
+        timestamp: Duration,
+        display_duration: Duration,
+    ) -> Result<(), ObsError> {
+        self.queue(QueuedCaption {
+            text: text.into(),
+            display_duration,
+            timestamp: Some(timestamp),
+        })
+    }
+
+    /// Returns how long the output has been active, which is the time captions are timestamped
+    /// against. `None` if the output is not active.
+    pub fn output_time(&self) -> Result<Option<Duration>, ObsError> {
+        let started_at = self
+            .started_at
+            .read()
+            .map_err(|e| ObsError::LockError(e.to_string()))?;
+
+        Ok(started_at.map(|started_at| started_at.elapsed()))
+    }
+
+    fn queue(&self, caption: QueuedCaption) -> Result<(), ObsError> {
+        self.sender
+            .as_ref()
+            .ok_or(ObsError::NoSenderError)?
+            .send(caption)
+            .map_err(|_| ObsError::Unexpected("The caption thread has stopped".to_string()))
+    }
+}
+
+#[cfg(feature = "enable_runtime")]
+impl Drop for ObsCaptionQueue {
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                log::warn!("The output caption thread panicked");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+fn caption(text: &str, timestamp: Option<u64>) -> QueuedCaption {
+    QueuedCaption {
+        text: text.to_string(),
+        display_duration: Duration::from_secs(2),
+        timestamp: timestamp.map(Duration::from_secs),
+    }
+}
+
+#[test]
+fn test_pending_captions_order() {
+    let mut pending = PendingCaptions::default();
+    pending.push(caption("third", Some(5)));
+    pending.push(caption("first", None));
+    pending.push(caption("second", Some(1)));
+    pending.push(caption("fourth", Some(5)));
+
+    let texts = |captions: Vec<QueuedCaption>| {
+        captions
+            .into_iter()
+            .map(|caption| caption.text)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(texts(pending.take_due(Duration::ZERO)), ["first"]);
+    assert_eq!(texts(pending.take_due(Duration::from_secs(2))), ["second"]);
+    assert!(pending.take_due(Duration::from_secs(4)).is_empty());
+    assert_eq!(
+        texts(pending.take_due(Duration::from_secs(5))),
+        ["third", "fourth"]
+    );
+}
+
+#[test]
+fn test_pending_captions_outdated() {
+    let mut pending = PendingCaptions::default();
+    pending.push(caption("outdated", Some(1)));
+    pending.push(caption("late", Some(2)));
+    pending.push(caption("untimed", None));
+
+    // The first caption would have been shown from 1 to 3 seconds
+    let due = pending.take_due(Duration::from_secs(3));
+    assert_eq!(due, [caption("untimed", None), caption("late", Some(2))]);
+    assert!(pending.captions.is_empty());
+}
@@ -8,6 +8,12 @@ macro_rules! forward_obs_output_impl {
                 self.$var_name.signals()
             }
 
+            fn file_signals(
+                &self,
+            ) -> Option<&std::sync::Arc<$crate::data::output::ObsOutputFileSignals>> {
+                self.$var_name.file_signals()
+            }
+
             fn video_encoder(
                 &self,
             ) -> &std::sync::Arc<
@@ -28,6 +34,14 @@ macro_rules! forward_obs_output_impl {
             > {
                 self.$var_name.audio_encoders()
             }
+
+            fn start_hook(
+                &self,
+            ) -> &std::sync::Arc<
+                std::sync::RwLock<Option<$crate::data::output::ObsOutputStartHook>>,
+            > {
+                self.$var_name.start_hook()
+            }
         }
 
         impl $struct_name {
@@ -25,6 +25,50 @@ pub use traits::*;
 mod replay_buffer;
 pub use replay_buffer::*;
 
+mod stats;
+pub use stats::*;
+
+mod reconnect;
+pub use reconnect::*;
+
+mod captions;
+#[cfg(feature = "enable_runtime")]
+pub use captions::*;
+
+/// Updates the settings of an output right before it is started, for example to generate a new
+/// path for every recording. See `ObsOutputTrait::set_start_hook`.
+#[derive(Clone)]
+pub struct ObsOutputStartHook(Arc<StartHookFn>);
+
+type StartHookFn = dyn Fn(&mut ObsData) -> Result<(), ObsError> + Send + Sync;
+
+impl ObsOutputStartHook {
+    /// Creates a hook from a function that sets the settings to change on the given data.
+    /// Settings that are not set keep their current value.
+    pub fn new<F>(hook: F) -> Self
+    where
+        F: Fn(&mut ObsData) -> Result<(), ObsError> + Send + Sync + 'static,
+    {
+        Self(Arc::new(hook))
+    }
+
+    pub(crate) fn call(&self, settings: &mut ObsData) -> Result<(), ObsError> {
+        (self.0)(settings)
+    }
+}
+
+impl std::fmt::Debug for ObsOutputStartHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObsOutputStartHook").finish_non_exhaustive()
+    }
+}
+
+/// Outputs that can split their recording into multiple files and emit the `file_changed` signal
+const FILE_SPLITTING_OUTPUTS: [&str; 3] = ["ffmpeg_muxer", "mp4_output", "mov_output"];
+
+/// Hybrid MP4 and MOV outputs, which can add chapter markers to their recording
+const CHAPTER_OUTPUTS: [&str; 2] = ["mp4_output", "mov_output"];
+
 #[derive(Debug)]
 struct _ObsOutputDropGuard {
     output: Sendable<*mut obs_output>,
@@ -57,6 +101,9 @@ pub struct ObsOutputRef {
     /// Disconnect signals first
     signal_manager: Arc<ObsOutputSignals>,
 
+    /// Signals of outputs that split their recording, see `FILE_SPLITTING_OUTPUTS`
+    file_signal_manager: Option<Arc<ObsOutputFileSignals>>,
+
     /// Settings for the output
     settings: Arc<RwLock<ImmutableObsData>>,
 
@@ -69,6 +116,9 @@ pub struct ObsOutputRef {
     /// Audio encoders attached to this output
     audio_encoders: Arc<RwLock<HashMap<usize, Arc<ObsAudioEncoder>>>>,
 
+    /// Called before the output is started
+    start_hook: Arc<RwLock<Option<ObsOutputStartHook>>>,
+
     /// The type identifier of this output
     id: ObsString,
 
@@ -160,12 +210,22 @@ impl ObsOutputTraitSealed for ObsOutputRef {
         };
 
         let signal_manager = ObsOutputSignals::new(&output, runtime.clone())?;
+        let file_signal_manager = if FILE_SPLITTING_OUTPUTS.iter().any(|o| id == *o) {
+            Some(Arc::new(ObsOutputFileSignals::new(
+                &output,
+                runtime.clone(),
+            )?))
+        } else {
+            None
+        };
+
         Ok(Self {
             settings: Arc::new(RwLock::new(settings)),
             hotkey_data: Arc::new(RwLock::new(hotkey_data)),
 
             curr_video_encoder: Arc::new(RwLock::new(None)),
             audio_encoders: Arc::new(RwLock::new(HashMap::new())),
+            start_hook: Arc::new(RwLock::new(None)),
 
             output: output.clone(),
             id,
@@ -173,6 +233,7 @@ impl ObsOutputTraitSealed for ObsOutputRef {
 
             runtime,
             signal_manager: Arc::new(signal_manager),
+            file_signal_manager,
         })
     }
 }
@@ -248,6 +309,10 @@ impl ObsOutputTrait for ObsOutputRef {
         &self.signal_manager
     }
 
+    fn file_signals(&self) -> Option<&Arc<ObsOutputFileSignals>> {
+        self.file_signal_manager.as_ref()
+    }
+
     fn video_encoder(&self) -> &Arc<RwLock<Option<Arc<ObsVideoEncoder>>>> {
         &self.curr_video_encoder
     }
@@ -255,6 +320,10 @@ impl ObsOutputTrait for ObsOutputRef {
     fn audio_encoders(&self) -> &Arc<RwLock<HashMap<usize, Arc<ObsAudioEncoder>>>> {
         &self.audio_encoders
     }
+
+    fn start_hook(&self) -> &Arc<RwLock<Option<ObsOutputStartHook>>> {
+        &self.start_hook
+    }
 }
 
 impl_signal_manager!(|ptr: SmartPointerSendable<*mut libobs::obs_output>| unsafe {
@@ -265,10 +334,23 @@ impl_signal_manager!(|ptr: SmartPointerSendable<*mut libobs::obs_output>| unsafe
     "stop": {code: crate::enums::ObsOutputStopSignal},
     "pause": {},
     "unpause": {},
+    /// Emitted when an output with an active delay starts, before the delayed data is sent
     "starting": {},
+    /// Emitted when an output with an active delay is stopped, while the remaining delayed data is sent
     "stopping": {},
     "activate": {},
     "deactivate": {},
-    "reconnect": {},
+    /// Emitted when an output that supports reconnecting lost its connection,
+    /// with the number of seconds until the next attempt
+    "reconnect": {timeout_sec: i64},
+    /// Emitted when an output reconnected successfully
     "reconnect_success": {}
 ]);
+
+impl_signal_manager!(|ptr: SmartPointerSendable<*mut libobs::obs_output>| unsafe {
+    // Safety: We are using a smart pointer, so it is fine
+    libobs::obs_output_get_signal_handler(ptr.get_ptr())
+}, ObsOutputFileSignals for *mut libobs::obs_output, [
+    /// Emitted when the output starts writing to the next file, with the path of that file
+    "file_changed": {next_file: String}
+]);
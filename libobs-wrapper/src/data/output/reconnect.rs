@@ -0,0 +1,326 @@
+//! Reconnecting outputs that stopped unexpectedly.
+//!
+//! Outputs that support it (like `rtmp_output`) reconnect on their own, which is configured with
+//! `ObsOutputTrait::set_reconnect_settings`. For every other output an `ObsReconnectPolicy` can be
+//! used to restart the output from Rust with an exponential backoff.
+
+use std::{fmt, sync::Arc, time::Duration};
+
+#[cfg(feature = "enable_runtime")]
+use std::{
+    sync::mpsc::{self, RecvTimeoutError},
+    thread::JoinHandle,
+};
+#[cfg(feature = "enable_runtime")]
+use tokio::sync::broadcast::{self, error::TryRecvError};
+
+use crate::enums::ObsOutputStopSignal;
+#[cfg(feature = "enable_runtime")]
+use crate::utils::ObsError;
+
+#[cfg(feature = "enable_runtime")]
+use super::ObsOutputTrait;
+
+/// How often the reconnect thread checks for new signals of the output
+#[cfg(feature = "enable_runtime")]
+const SIGNAL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+type GiveUpCallback = Arc<dyn Fn(ObsOutputStopSignal, u32) + Send + Sync>;
+
+/// Describes when and how often an output should be restarted after it stopped unexpectedly.
+///
+/// The delay before the first attempt is `initial_delay` and is multiplied by `multiplier`
+/// for every further attempt, up to `max_delay`.
+///
+/// ```ignore
+/// let policy = ObsReconnectPolicy::new()
+///     .set_max_attempts(5)
+///     .set_initial_delay(Duration::from_secs(1))
+///     .on_give_up(|code, attempts| println!("Gave up after {} attempts: {}", attempts, code));
+///
+/// let watcher = output.start_reconnect_policy(policy)?;
+/// ```
+#[derive(Clone)]
+pub struct ObsReconnectPolicy {
+    max_attempts: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: f64,
+    retry_on: Vec<ObsOutputStopSignal>,
+    give_up: Option<GiveUpCallback>,
+}
+
+impl Default for ObsReconnectPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ObsReconnectPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObsReconnectPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_delay", &self.initial_delay)
+            .field("max_delay", &self.max_delay)
+            .field("multiplier", &self.multiplier)
+            .field("retry_on", &self.retry_on)
+            .field("give_up", &self.give_up.is_some())
+            .finish()
+    }
+}
+
+impl ObsReconnectPolicy {
+    /// Creates a policy with 10 attempts, starting with a delay of 2 seconds that doubles
+    /// with every attempt up to one minute. The output is restarted when it was disconnected,
+    /// couldn't connect or stopped because of a generic error.
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 10,
+            initial_delay: Duration::from_secs(2),
+            max_delay: Duration::from_secs(60),
+            multiplier: 2.0,
+            retry_on: vec![
+                ObsOutputStopSignal::Disconnected,
+                ObsOutputStopSignal::ConnectFailed,
+                ObsOutputStopSignal::Error,
+            ],
+            give_up: None,
+        }
+    }
+
+    /// Sets how often the output is restarted before giving up.
+    pub fn set_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets the delay before the first attempt.
+    pub fn set_initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Sets the longest delay between two attempts.
+    pub fn set_max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Sets the factor the delay is multiplied by after every attempt. Values below 1 are set to 1.
+    pub fn set_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Sets the stop codes after which the output is restarted.
+    pub fn set_retry_on(mut self, codes: &[ObsOutputStopSignal]) -> Self {
+        self.retry_on = codes.to_vec();
+        self
+    }
+
+    /// Sets a callback that is called with the last stop code and the number of attempts
+    /// when the output couldn't be restarted within `max_attempts`.
+    pub fn on_give_up<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(ObsOutputStopSignal, u32) + Send + Sync + 'static,
+    {
+        self.give_up = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns whether the output should be restarted after it stopped with the given code.
+    pub fn should_retry(&self, code: ObsOutputStopSignal) -> bool {
+        self.retry_on.contains(&code)
+    }
+
+    /// Returns the delay before the given attempt, starting at 1.
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        if attempt == 0 {
+            return Duration::ZERO;
+        }
+
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let secs = self.initial_delay.as_secs_f64() * self.multiplier.powi(exponent);
+
+        Duration::try_from_secs_f64(secs.min(self.max_delay.as_secs_f64()))
+            .unwrap_or(self.max_delay)
+    }
+}
+
+/// Events sent by an `ObsReconnectWatcher`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ObsReconnectEvent {
+    /// The output stopped with `code` and is restarted after `delay`
+    Retrying {
+        attempt: u32,
+        delay: Duration,
+        code: ObsOutputStopSignal,
+    },
+    /// The output started again after the given number of attempts
+    Reconnected { attempts: u32 },
+    /// The output couldn't be restarted, the watcher stops afterwards
+    GaveUp {
+        attempts: u32,
+        code: ObsOutputStopSignal,
+    },
+}
+
+/// Restarts an output according to an `ObsReconnectPolicy` on a background thread.
+/// The watcher keeps the output alive and is stopped when it is dropped.
+#[cfg(feature = "enable_runtime")]
+#[derive(Debug)]
+pub struct ObsReconnectWatcher {
+    sender: broadcast::Sender<ObsReconnectEvent>,
+    /// Dropping this sender stops the thread
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+#[cfg(feature = "enable_runtime")]
+pub(super) fn try_recv_signal<T: Clone>(rx: &mut broadcast::Receiver<T>) -> Option<T> {
+    loop {
+        match rx.try_recv() {
+            Ok(value) => return Some(value),
+            Err(TryRecvError::Lagged(_)) => continue,
+            Err(_) => return None,
+        }
+    }
+}
+
+#[cfg(feature = "enable_runtime")]
+impl ObsReconnectWatcher {
+    pub(crate) fn new<O>(output: O, policy: ObsReconnectPolicy) -> Result<Self, ObsError>
+    where
+        O: ObsOutputTrait + Clone + 'static,
+    {
+        let mut start_signals = output.signals().on_start()?;
+        let mut stop_signals = output.signals().on_stop()?;
+
+        let (sender, _) = broadcast::channel(16);
+        let (stop, stop_rx) = mpsc::channel::<()>();
+
+        let thread_sender = sender.clone();
+        let thread = std::thread::Builder::new()
+            .name("obs-output-reconnect".to_string())
+            .spawn(move || {
+                let mut attempt = 0;
+
+                'watch: while let Err(RecvTimeoutError::Timeout) =
+                    stop_rx.recv_timeout(SIGNAL_POLL_INTERVAL)
+                {
+                    while try_recv_signal(&mut start_signals).is_some() {
+                        if attempt > 0 {
+                            let _ = thread_sender
+                                .send(ObsReconnectEvent::Reconnected { attempts: attempt });
+                            attempt = 0;
+                        }
+                    }
+
+                    let Some(code) = try_recv_signal(&mut stop_signals) else {
+                        continue;
+                    };
+
+                    if !policy.should_retry(code) {
+                        attempt = 0;
+                        continue;
+                    }
+
+                    loop {
+                        attempt += 1;
+                        if attempt > policy.max_attempts {
+                            let attempts = attempt - 1;
+                            log::warn!(
+                                "Giving up restarting the output after {} attempts",
+                                attempts
+                            );
+                            if let Some(give_up) = &policy.give_up {
+                                give_up(code, attempts);
+                            }
+
+                            let _ =
+                                thread_sender.send(ObsReconnectEvent::GaveUp { attempts, code });
+                            break 'watch;
+                        }
+
+                        let delay = policy.delay_for_attempt(attempt);
+                        // There might be no receivers at the moment
+                        let _ = thread_sender.send(ObsReconnectEvent::Retrying {
+                            attempt,
+                            delay,
+                            code,
+                        });
+
+                        if !matches!(stop_rx.recv_timeout(delay), Err(RecvTimeoutError::Timeout)) {
+                            break 'watch;
+                        }
+
+                        // A failed start of an output that connects asynchronously is reported
+                        // by another stop signal, so only synchronous failures are retried here
+                        match output.start() {
+                            Ok(()) => break,
+                            Err(e) => log::warn!("Failed to restart output: {:?}", e),
+                        }
+                    }
+                }
+            })
+            .map_err(|e| ObsError::IoError(e.to_string()))?;
+
+        Ok(Self {
+            sender,
+            stop: Some(stop),
+            thread: Some(thread),
+        })
+    }
+
+    /// Returns a receiver for the reconnect events.
+    pub fn subscribe(&self) -> broadcast::Receiver<ObsReconnectEvent> {
+        self.sender.subscribe()
+    }
+}
+
+#[cfg(feature = "enable_runtime")]
+impl Drop for ObsReconnectWatcher {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                log::warn!("The output reconnect thread panicked");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_reconnect_delay() {
+    let policy = ObsReconnectPolicy::new()
+        .set_initial_delay(Duration::from_secs(1))
+        .set_max_delay(Duration::from_secs(10))
+        .set_multiplier(2.0);
+
+    assert_eq!(policy.delay_for_attempt(0), Duration::ZERO);
+    assert_eq!(policy.delay_for_attempt(1), Duration::from_secs(1));
+    assert_eq!(policy.delay_for_attempt(2), Duration::from_secs(2));
+    assert_eq!(policy.delay_for_attempt(4), Duration::from_secs(8));
+    assert_eq!(policy.delay_for_attempt(5), Duration::from_secs(10));
+    assert_eq!(policy.delay_for_attempt(u32::MAX), Duration::from_secs(10));
+
+    // Multipliers below 1 would shrink the delay
+    let policy = policy.set_multiplier(0.5);
+    assert_eq!(policy.delay_for_attempt(3), Duration::from_secs(1));
+}
+
+#[test]
+fn test_reconnect_retry_on() {
+    let policy = ObsReconnectPolicy::new();
+    assert!(policy.should_retry(ObsOutputStopSignal::Disconnected));
+    assert!(!policy.should_retry(ObsOutputStopSignal::Success));
+    assert!(!policy.should_retry(ObsOutputStopSignal::NoSpace));
+
+    let policy = policy.set_retry_on(&[ObsOutputStopSignal::NoSpace]);
+    assert!(policy.should_retry(ObsOutputStopSignal::NoSpace));
+    assert!(!policy.should_retry(ObsOutputStopSignal::Disconnected));
+}
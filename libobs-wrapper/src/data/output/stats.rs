@@ -0,0 +1,204 @@
+//! Statistics of outputs and of the rendering of OBS, see `ObsOutputTrait::get_stats`.
+
+use getters0::Getters;
+use libobs::obs_output;
+
+#[cfg(feature = "enable_runtime")]
+use std::{
+    sync::mpsc::{self, RecvTimeoutError},
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+#[cfg(feature = "enable_runtime")]
+use tokio::sync::broadcast;
+
+use crate::{
+    run_with_obs, runtime::ObsRuntime, unsafe_send::SmartPointerSendable, utils::ObsError,
+};
+
+/// A snapshot of the statistics of an output, together with the render statistics of OBS.
+///
+/// The render statistics (`render_*` and `encoding_*`) are global and the same for every output.
+#[derive(Debug, Clone, PartialEq, Getters)]
+#[skip_new]
+pub struct ObsOutputStats {
+    /// Bytes written by the output since it was started
+    total_bytes: u64,
+    /// Frames sent by the output since it was started
+    total_frames: i32,
+    /// Frames dropped by the output, for example because of network congestion
+    frames_dropped: i32,
+    /// Congestion of the network connection from 0 to 1, always 0 for outputs that don't stream
+    congestion: f32,
+    /// The time it took to connect to the server, 0 for outputs that don't stream
+    connect_time_ms: i32,
+    /// Whether the output is currently trying to reconnect
+    reconnecting: bool,
+    /// The average bitrate since the previous sample of an `ObsOutputStatsSampler`.
+    /// `None` if the stats were not collected by a sampler or for the first sample.
+    bitrate_kbps: Option<f64>,
+    /// Frames rendered by OBS
+    render_total_frames: u32,
+    /// Frames that OBS couldn't render in time
+    render_lagged_frames: u32,
+    /// Frames that were passed on to the encoders
+    encoding_total_frames: u32,
+    /// Frames that were skipped because the encoders couldn't keep up
+    encoding_skipped_frames: u32,
+    /// The current frame rate of the rendering
+    active_fps: f64,
+    /// The average time it takes to render a frame
+    average_frame_time_ns: u64,
+}
+
+impl ObsOutputStats {
+    /// Collects the current statistics of the given output.
+    pub(crate) fn collect(
+        output: SmartPointerSendable<*mut obs_output>,
+        runtime: &ObsRuntime,
+    ) -> Result<Self, ObsError> {
+        run_with_obs!(runtime, (output), move || unsafe {
+            // Safety: The output pointer is valid because of the smart pointer
+            // and the video output is checked for null.
+            let output_ptr = output.get_ptr();
+            let video = libobs::obs_get_video();
+            let (encoding_total_frames, encoding_skipped_frames) = if video.is_null() {
+                (0, 0)
+            } else {
+                (
+                    libobs::video_output_get_total_frames(video),
+                    libobs::video_output_get_skipped_frames(video),
+                )
+            };
+
+            Self {
+                total_bytes: libobs::obs_output_get_total_bytes(output_ptr),
+                total_frames: libobs::obs_output_get_total_frames(output_ptr),
+                frames_dropped: libobs::obs_output_get_frames_dropped(output_ptr),
+                congestion: libobs::obs_output_get_congestion(output_ptr),
+                connect_time_ms: libobs::obs_output_get_connect_time_ms(output_ptr),
+                reconnecting: libobs::obs_output_reconnecting(output_ptr),
+                bitrate_kbps: None,
+                render_total_frames: libobs::obs_get_total_frames(),
+                render_lagged_frames: libobs::obs_get_lagged_frames(),
+                encoding_total_frames,
+                encoding_skipped_frames,
+                active_fps: libobs::obs_get_active_fps(),
+                average_frame_time_ns: libobs::obs_get_average_frame_time_ns(),
+            }
+        })
+    }
+}
+
+/// Computes the average bitrate between two samples. Returns `None` if the byte count was reset
+/// (e.g. because the output was restarted) or no time has passed.
+#[cfg_attr(not(feature = "enable_runtime"), allow(dead_code))]
+fn estimate_bitrate_kbps(previous_bytes: u64, bytes: u64, elapsed_secs: f64) -> Option<f64> {
+    if bytes < previous_bytes || elapsed_secs <= 0.0 {
+        return None;
+    }
+
+    Some((bytes - previous_bytes) as f64 * 8.0 / 1000.0 / elapsed_secs)
+}
+
+/// Collects the statistics of an output periodically on a background thread.
+/// The sampler is stopped when it is dropped.
+///
+/// ```ignore
+/// let sampler = output.start_stats_sampler(Duration::from_secs(1))?;
+/// let mut rx = sampler.subscribe();
+///
+/// while let Ok(stats) = rx.blocking_recv() {
+///     println!("{:?} kbps, {} frames dropped", stats.bitrate_kbps(), stats.frames_dropped());
+/// }
+/// ```
+#[cfg(feature = "enable_runtime")]
+#[derive(Debug)]
+pub struct ObsOutputStatsSampler {
+    sender: broadcast::Sender<ObsOutputStats>,
+    /// Dropping this sender stops the thread
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+#[cfg(feature = "enable_runtime")]
+impl ObsOutputStatsSampler {
+    pub(crate) fn new(
+        output: SmartPointerSendable<*mut obs_output>,
+        runtime: ObsRuntime,
+        interval: Duration,
+    ) -> Result<Self, ObsError> {
+        if interval.is_zero() {
+            return Err(ObsError::InvalidOperation(
+                "The interval of the stats sampler must not be zero".to_string(),
+            ));
+        }
+
+        let (sender, _) = broadcast::channel(16);
+        let (stop, stop_rx) = mpsc::channel::<()>();
+
+        let thread_sender = sender.clone();
+        let thread = std::thread::Builder::new()
+            .name("obs-output-stats".to_string())
+            .spawn(move || {
+                let mut previous: Option<(u64, Instant)> = None;
+                while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(interval) {
+                    let mut stats = match ObsOutputStats::collect(output.clone(), &runtime) {
+                        Ok(stats) => stats,
+                        Err(e) => {
+                            log::warn!("Failed to collect output stats: {:?}", e);
+                            continue;
+                        }
+                    };
+
+                    let now = Instant::now();
+                    if let Some((previous_bytes, previous_time)) = previous {
+                        stats.bitrate_kbps = estimate_bitrate_kbps(
+                            previous_bytes,
+                            stats.total_bytes,
+                            now.duration_since(previous_time).as_secs_f64(),
+                        );
+                    }
+
+                    previous = Some((stats.total_bytes, now));
+
+                    // There might be no receivers at the moment
+                    let _ = thread_sender.send(stats);
+                }
+            })
+            .map_err(|e| ObsError::IoError(e.to_string()))?;
+
+        Ok(Self {
+            sender,
+            stop: Some(stop),
+            thread: Some(thread),
+        })
+    }
+
+    /// Returns a receiver for the collected statistics.
+    pub fn subscribe(&self) -> broadcast::Receiver<ObsOutputStats> {
+        self.sender.subscribe()
+    }
+}
+
+#[cfg(feature = "enable_runtime")]
+impl Drop for ObsOutputStatsSampler {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                log::warn!("The output stats thread panicked");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_estimate_bitrate() {
+    assert_eq!(estimate_bitrate_kbps(0, 750_000, 1.0), Some(6000.0));
+    assert_eq!(estimate_bitrate_kbps(1000, 126_000, 0.5), Some(2000.0));
+
+    // The output was restarted
+    assert_eq!(estimate_bitrate_kbps(5000, 1000, 1.0), None);
+    assert_eq!(estimate_bitrate_kbps(0, 1000, 0.0), None);
+}
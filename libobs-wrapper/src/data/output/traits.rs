@@ -3,19 +3,45 @@ use std::{
     ffi::CStr,
     fmt::Debug,
     sync::{Arc, RwLock},
+    time::Duration,
 };
 
+use libobs::proc_handler_t;
+
 use crate::{
-    data::object::ObsObjectTrait,
+    data::{object::ObsObjectTrait, ImmutableObsData, ObsData, ObsDataPointers},
     encoders::{audio::ObsAudioEncoder, video::ObsVideoEncoder},
-    enums::ObsOutputStopSignal,
+    enums::{ObsAudioTracks, ObsOutputDelayFlags, ObsOutputStopMode, ObsOutputStopSignal},
     macros::trait_with_optional_send_sync,
     run_with_obs,
     runtime::ObsRuntime,
-    utils::{AudioEncoderInfo, ObsError, OutputInfo, VideoEncoderInfo},
+    unsafe_send::{Sendable, SmartPointerSendable},
+    utils::{AudioEncoderInfo, ObsCalldataExt, ObsError, ObsString, OutputInfo, VideoEncoderInfo},
+};
+
+#[cfg(feature = "enable_runtime")]
+use super::{ObsCaptionQueue, ObsOutputStatsSampler, ObsReconnectPolicy, ObsReconnectWatcher};
+use super::{
+    ObsOutputFileSignals, ObsOutputSignals, ObsOutputStartHook, ObsOutputStats, CHAPTER_OUTPUTS,
 };
 
-use super::ObsOutputSignals;
+/// Returns the procedure handler of an output, which is used to call procedures like `split_file`.
+fn get_proc_handler(
+    output_ptr: SmartPointerSendable<*mut libobs::obs_output_t>,
+    runtime: &ObsRuntime,
+) -> Result<Sendable<*mut proc_handler_t>, ObsError> {
+    run_with_obs!(runtime, (output_ptr), move || {
+        // Safety: output_ptr is valid because of SmartPointer
+        let ph = unsafe { libobs::obs_output_get_proc_handler(output_ptr.get_ptr()) };
+        if ph.is_null() {
+            return Err(ObsError::NullPointer(Some(
+                "Failed to get proc handler.".to_string(),
+            )));
+        }
+
+        Ok(Sendable(ph))
+    })?
+}
 
 trait_with_optional_send_sync! {
     pub(crate) trait ObsOutputTraitSealed: Debug {
@@ -37,8 +63,24 @@ trait_with_optional_send_sync! {
 pub trait ObsOutputTrait: ObsOutputTraitSealed + ObsObjectTrait<*mut libobs::obs_output_t> {
     fn signals(&self) -> &Arc<ObsOutputSignals>;
 
+    /// The signals of outputs that can split their recording into multiple files,
+    /// `None` for every other output.
+    fn file_signals(&self) -> Option<&Arc<ObsOutputFileSignals>>;
+
     fn video_encoder(&self) -> &Arc<RwLock<Option<Arc<ObsVideoEncoder>>>>;
     fn audio_encoders(&self) -> &Arc<RwLock<HashMap<usize, Arc<ObsAudioEncoder>>>>;
+    fn start_hook(&self) -> &Arc<RwLock<Option<ObsOutputStartHook>>>;
+
+    /// Sets a hook that updates the settings of this output every time before it is started,
+    /// for example to record every session to a new file. `None` removes the hook.
+    fn set_start_hook(&self, hook: Option<ObsOutputStartHook>) -> Result<(), ObsError> {
+        *self
+            .start_hook()
+            .write()
+            .map_err(|e| ObsError::LockError(e.to_string()))? = hook;
+
+        Ok(())
+    }
 
     /// Returns the current video encoder attached to this output, if any.
     fn get_current_video_encoder(&self) -> Result<Option<Arc<ObsVideoEncoder>>, ObsError> {
@@ -143,6 +185,98 @@ pub trait ObsOutputTrait: ObsOutputTraitSealed + ObsObjectTrait<*mut libobs::obs
         Ok(())
     }
 
+    /// Creates an audio encoder for every given track and attaches them to the consecutive
+    /// encoder slots of this output, so multi-track outputs like `ffmpeg_muxer` record one
+    /// audio stream per track. Encoders of previously attached tracks are detached.
+    ///
+    /// `encoder_info` is called with the mixer index (track - 1) of every track. Muxers use
+    /// the name of the encoder as the title of its audio stream.
+    ///
+    /// Fails if the output is active.
+    fn create_and_set_audio_tracks<F>(
+        &mut self,
+        tracks: ObsAudioTracks,
+        mut encoder_info: F,
+    ) -> Result<Vec<Arc<ObsAudioEncoder>>, ObsError>
+    where
+        F: FnMut(usize) -> Result<AudioEncoderInfo, ObsError>,
+        Self: Sized,
+    {
+        if self.is_active()? {
+            return Err(ObsError::OutputAlreadyActive);
+        }
+
+        let mut encoders = Vec::new();
+        for (slot, mixer_idx) in tracks.mixer_indices().into_iter().enumerate() {
+            let encoder = ObsAudioEncoder::new_from_info(
+                encoder_info(mixer_idx)?,
+                mixer_idx,
+                self.runtime().clone(),
+            )?;
+            self.set_audio_encoder(encoder.clone(), slot)?;
+            encoders.push(encoder);
+        }
+
+        let unused_slots = self
+            .audio_encoders()
+            .read()
+            .map_err(|e| ObsError::LockError(e.to_string()))?
+            .keys()
+            .copied()
+            .filter(|slot| *slot >= encoders.len())
+            .collect::<Vec<_>>();
+
+        let output_ptr = self.as_ptr();
+        let runtime = self.runtime().clone();
+        run_with_obs!(runtime, (output_ptr, unused_slots), move || {
+            for slot in unused_slots {
+                unsafe {
+                    // Safety: output_ptr is valid because of SmartPointer
+                    libobs::obs_output_set_audio_encoder(
+                        output_ptr.get_ptr(),
+                        std::ptr::null_mut(),
+                        slot,
+                    );
+                }
+            }
+        })?;
+
+        let track_count = encoders.len();
+        self.audio_encoders()
+            .write()
+            .map_err(|e| ObsError::LockError(e.to_string()))?
+            .retain(|slot, _| *slot < track_count);
+
+        Ok(encoders)
+    }
+
+    /// Sets the audio tracks of outputs that receive raw audio with multiple tracks.
+    /// Outputs with audio encoders use the tracks of their encoders instead.
+    fn set_mixers(&self, tracks: ObsAudioTracks) -> Result<(), ObsError> {
+        let output_ptr = self.as_ptr();
+        let runtime = self.runtime().clone();
+        run_with_obs!(runtime, (output_ptr), move || {
+            unsafe {
+                // Safety: output_ptr is valid because of SmartPointer
+                libobs::obs_output_set_mixers(output_ptr.get_ptr(), tracks.bits() as usize)
+            }
+        })
+    }
+
+    /// Returns the audio tracks of an output that receives raw audio, see `set_mixers`.
+    fn get_mixers(&self) -> Result<ObsAudioTracks, ObsError> {
+        let output_ptr = self.as_ptr();
+        let runtime = self.runtime().clone();
+        let mixers = run_with_obs!(runtime, (output_ptr), move || {
+            unsafe {
+                // Safety: output_ptr is valid because of SmartPointer
+                libobs::obs_output_get_mixers(output_ptr.get_ptr())
+            }
+        })?;
+
+        Ok(ObsAudioTracks::from_bits_truncate(mixers as u32))
+    }
+
     /// Starts the output, wiring encoders to global contexts and invoking obs_output_start.
     /// Returns an error with last OBS message when start fails.
     fn start(&self) -> Result<(), ObsError> {
@@ -150,6 +284,36 @@ pub trait ObsOutputTrait: ObsOutputTraitSealed + ObsObjectTrait<*mut libobs::obs
             return Err(ObsError::OutputAlreadyActive);
         }
 
+        let start_hook = self
+            .start_hook()
+            .read()
+            .map_err(|e| ObsError::LockError(e.to_string()))?
+            .clone();
+        if let Some(start_hook) = start_hook {
+            let mut settings = ObsData::new(self.runtime().clone())?;
+            start_hook.call(&mut settings)?;
+
+            let settings_ptr = settings.as_ptr();
+            let output_ptr = self.as_ptr();
+            let new_settings_ptr =
+                run_with_obs!(self.runtime(), (output_ptr, settings_ptr), move || {
+                    unsafe {
+                        // Safety: Both pointers are valid because of the smart pointers,
+                        // obs_output_update only applies the given settings on top of the current ones.
+                        libobs::obs_output_update(output_ptr.get_ptr(), settings_ptr.get_ptr());
+                        Sendable(libobs::obs_output_get_settings(output_ptr.get_ptr()))
+                    }
+                })?;
+
+            if new_settings_ptr.0.is_null() {
+                return Err(ObsError::NullPointer(None));
+            }
+
+            let new_settings =
+                ImmutableObsData::from_raw_pointer(new_settings_ptr, self.runtime().clone());
+            self.__internal_replace_settings(new_settings)?;
+        }
+
         let vid_encoder_ptr = self
             .video_encoder()
             .read()
@@ -280,7 +444,17 @@ pub trait ObsOutputTrait: ObsOutputTraitSealed + ObsObjectTrait<*mut libobs::obs
     }
 
     /// Stops the output and waits for stop and deactivate signals.
+    ///
+    /// If a delay is active (see `set_delay`), the output emits the `stopping` signal and keeps sending the
+    /// delayed data, so this blocks until the delay has elapsed. Use `stop_with_mode` to stop immediately.
     fn stop(&mut self) -> Result<(), ObsError> {
+        self.stop_with_mode(ObsOutputStopMode::Drain)
+    }
+
+    /// Stops the output with the given mode and waits for stop and deactivate signals.
+    ///
+    /// This can also be used to force a draining output to stop, while another thread is waiting in `stop`.
+    fn stop_with_mode(&mut self, mode: ObsOutputStopMode) -> Result<(), ObsError> {
         let output_ptr = self.as_ptr();
         let runtime = self.runtime().clone();
         let output_active = run_with_obs!(runtime, (output_ptr), move || {
@@ -303,7 +477,10 @@ pub trait ObsOutputTrait: ObsOutputTraitSealed + ObsObjectTrait<*mut libobs::obs
         run_with_obs!(runtime, (output_ptr), move || {
             unsafe {
                 // Safety: output_ptr is valid because of SmartPointer
-                libobs::obs_output_stop(output_ptr.get_ptr())
+                match mode {
+                    ObsOutputStopMode::Drain => libobs::obs_output_stop(output_ptr.get_ptr()),
+                    ObsOutputStopMode::Force => libobs::obs_output_force_stop(output_ptr.get_ptr()),
+                }
             }
         })?;
 
@@ -321,6 +498,111 @@ pub trait ObsOutputTrait: ObsOutputTraitSealed + ObsObjectTrait<*mut libobs::obs
         Ok(())
     }
 
+    /// Splits the recording of this output into a new file right away. Splitting has to be enabled
+    /// in the settings of the output (`split_file`) before it is started, which also allows splitting
+    /// by time (`max_time_sec`) and size (`max_size_mb`).
+    ///
+    /// The path of the new file is sent by `on_file_changed`.
+    fn split_file(&self) -> Result<(), ObsError> {
+        if self.file_signals().is_none() {
+            return Err(ObsError::InvalidOperation(
+                "This output can't split its recording into multiple files".to_string(),
+            ));
+        }
+
+        let proc_handler = get_proc_handler(self.as_ptr(), self.runtime())?;
+
+        // Safety: We know that the proc handler is valid because we got it from OBS earlier
+        let mut calldata = unsafe {
+            self.runtime()
+                .call_proc_handler(&proc_handler, "split_file")?
+        };
+        if !calldata.get_bool("split_file_enabled")? {
+            return Err(ObsError::InvalidOperation(
+                "File splitting is not enabled for this output".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Adds a chapter marker with the given name at the current position of the recording.
+    /// Only hybrid MP4 and MOV recordings (`mp4_output`, `mov_output`) support chapters
+    /// and only while they are active.
+    fn add_chapter(&self, name: &str) -> Result<(), ObsError> {
+        let id = self.id();
+        if !CHAPTER_OUTPUTS.iter().any(|o| id == *o) {
+            return Err(ObsError::InvalidOperation(format!(
+                "The output {} doesn't support chapter markers",
+                id
+            )));
+        }
+
+        if !self.is_active()? {
+            return Err(ObsError::InvalidOperation(
+                "Chapters can only be added while the output is active".to_string(),
+            ));
+        }
+
+        let proc_handler = get_proc_handler(self.as_ptr(), self.runtime())?;
+
+        // Safety: We know that the proc handler is valid because we got it from OBS earlier
+        let res = unsafe {
+            self.runtime().call_proc_handler_with_strings(
+                &proc_handler,
+                "add_chapter",
+                &[("chapter_name", name)],
+            )
+        };
+
+        match res {
+            Ok(_) => Ok(()),
+            // The call fails if the output doesn't have the procedure
+            Err(ObsError::Unexpected(_)) => Err(ObsError::InvalidOperation(format!(
+                "The output {} doesn't support chapter markers",
+                id
+            ))),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Sends caption text to this output, which OBS inserts as CEA-608 captions into the video
+    /// with the next frame and shows for `display_duration`. Longer texts are split into lines
+    /// of 32 characters. Only active outputs accept captions.
+    fn send_caption_text(&self, text: &str, display_duration: Duration) -> Result<(), ObsError> {
+        if !self.is_active()? {
+            return Err(ObsError::InvalidOperation(
+                "Captions can only be sent while the output is active".to_string(),
+            ));
+        }
+
+        let text = ObsString::from(text);
+        let display_duration = display_duration.as_secs_f64();
+        let output_ptr = self.as_ptr();
+        run_with_obs!(self.runtime(), (output_ptr, text), move || {
+            unsafe {
+                // Safety: output_ptr is valid because of SmartPointer and the text is owned by this closure
+                libobs::obs_output_output_caption_text2(
+                    output_ptr.get_ptr(),
+                    text.as_ptr().0,
+                    display_duration,
+                )
+            }
+        })
+    }
+
+    /// Returns a receiver for the paths of the files this output writes to after splitting,
+    /// see `split_file`. Fails for outputs that can't split their recording.
+    fn on_file_changed(&self) -> Result<tokio::sync::broadcast::Receiver<String>, ObsError> {
+        self.file_signals()
+            .ok_or_else(|| {
+                ObsError::InvalidOperation(
+                    "This output can't split its recording into multiple files".to_string(),
+                )
+            })?
+            .on_file_changed()
+    }
+
     /// Returns whether the output is currently active.
     fn is_active(&self) -> Result<bool, ObsError> {
         let output_ptr = self.as_ptr();
@@ -334,4 +616,183 @@ pub trait ObsOutputTrait: ObsOutputTraitSealed + ObsObjectTrait<*mut libobs::obs
 
         Ok(output_active)
     }
+
+    /// Sets the delay of this output in seconds. The delay is applied the next time the output is started.
+    /// A delay of 0 disables it.
+    ///
+    /// While a delay is active, the output emits the `starting` and `stopping` signals
+    /// when it starts and stops sending the delayed data.
+    fn set_delay(&self, delay_sec: u32, flags: ObsOutputDelayFlags) -> Result<(), ObsError> {
+        let output_ptr = self.as_ptr();
+        let runtime = self.runtime().clone();
+        run_with_obs!(runtime, (output_ptr), move || {
+            unsafe {
+                // Safety: output_ptr is valid because of SmartPointer
+                libobs::obs_output_set_delay(output_ptr.get_ptr(), delay_sec, flags.bits())
+            }
+        })
+    }
+
+    /// Returns the delay in seconds that was set with `set_delay`.
+    fn get_delay(&self) -> Result<u32, ObsError> {
+        let output_ptr = self.as_ptr();
+        let runtime = self.runtime().clone();
+        run_with_obs!(runtime, (output_ptr), move || {
+            unsafe {
+                // Safety: output_ptr is valid because of SmartPointer
+                libobs::obs_output_get_delay(output_ptr.get_ptr())
+            }
+        })
+    }
+
+    /// Returns the delay in seconds the output is currently running with, which is 0 if it is not active.
+    /// This differs from `get_delay` if the delay was changed while the output was active.
+    fn get_active_delay(&self) -> Result<u32, ObsError> {
+        let output_ptr = self.as_ptr();
+        let runtime = self.runtime().clone();
+        run_with_obs!(runtime, (output_ptr), move || {
+            unsafe {
+                // Safety: output_ptr is valid because of SmartPointer
+                libobs::obs_output_get_active_delay(output_ptr.get_ptr())
+            }
+        })
+    }
+
+    /// Returns the number of bytes written by this output since it was started.
+    fn get_total_bytes(&self) -> Result<u64, ObsError> {
+        let output_ptr = self.as_ptr();
+        let runtime = self.runtime().clone();
+        run_with_obs!(runtime, (output_ptr), move || {
+            unsafe {
+                // Safety: output_ptr is valid because of SmartPointer
+                libobs::obs_output_get_total_bytes(output_ptr.get_ptr())
+            }
+        })
+    }
+
+    /// Returns the number of frames sent by this output since it was started.
+    fn get_total_frames(&self) -> Result<i32, ObsError> {
+        let output_ptr = self.as_ptr();
+        let runtime = self.runtime().clone();
+        run_with_obs!(runtime, (output_ptr), move || {
+            unsafe {
+                // Safety: output_ptr is valid because of SmartPointer
+                libobs::obs_output_get_total_frames(output_ptr.get_ptr())
+            }
+        })
+    }
+
+    /// Returns the number of frames dropped by this output, for example because of network congestion.
+    fn get_frames_dropped(&self) -> Result<i32, ObsError> {
+        let output_ptr = self.as_ptr();
+        let runtime = self.runtime().clone();
+        run_with_obs!(runtime, (output_ptr), move || {
+            unsafe {
+                // Safety: output_ptr is valid because of SmartPointer
+                libobs::obs_output_get_frames_dropped(output_ptr.get_ptr())
+            }
+        })
+    }
+
+    /// Returns the congestion of the network connection from 0 to 1. Always 0 for outputs that don't stream.
+    fn get_congestion(&self) -> Result<f32, ObsError> {
+        let output_ptr = self.as_ptr();
+        let runtime = self.runtime().clone();
+        run_with_obs!(runtime, (output_ptr), move || {
+            unsafe {
+                // Safety: output_ptr is valid because of SmartPointer
+                libobs::obs_output_get_congestion(output_ptr.get_ptr())
+            }
+        })
+    }
+
+    /// Returns the time it took to connect to the server. Always 0 for outputs that don't stream.
+    fn get_connect_time_ms(&self) -> Result<i32, ObsError> {
+        let output_ptr = self.as_ptr();
+        let runtime = self.runtime().clone();
+        run_with_obs!(runtime, (output_ptr), move || {
+            unsafe {
+                // Safety: output_ptr is valid because of SmartPointer
+                libobs::obs_output_get_connect_time_ms(output_ptr.get_ptr())
+            }
+        })
+    }
+
+    /// Returns whether this output is currently trying to reconnect.
+    fn is_reconnecting(&self) -> Result<bool, ObsError> {
+        let output_ptr = self.as_ptr();
+        let runtime = self.runtime().clone();
+        run_with_obs!(runtime, (output_ptr), move || {
+            unsafe {
+                // Safety: output_ptr is valid because of SmartPointer
+                libobs::obs_output_reconnecting(output_ptr.get_ptr())
+            }
+        })
+    }
+
+    /// Configures how outputs that support it (like `rtmp_output`) reconnect on their own after
+    /// losing their connection. OBS doubles the delay after every failed attempt.
+    /// A `retry_count` of 0 disables reconnecting.
+    fn set_reconnect_settings(
+        &self,
+        retry_count: u32,
+        retry_delay_sec: u32,
+    ) -> Result<(), ObsError> {
+        let retry_count = retry_count.min(i32::MAX as u32) as i32;
+        let retry_delay_sec = retry_delay_sec.min(i32::MAX as u32) as i32;
+
+        let output_ptr = self.as_ptr();
+        let runtime = self.runtime().clone();
+        run_with_obs!(runtime, (output_ptr), move || {
+            unsafe {
+                // Safety: output_ptr is valid because of SmartPointer
+                libobs::obs_output_set_reconnect_settings(
+                    output_ptr.get_ptr(),
+                    retry_count,
+                    retry_delay_sec,
+                )
+            }
+        })
+    }
+
+    /// Collects every statistic of this output at once, together with the render statistics of OBS.
+    fn get_stats(&self) -> Result<ObsOutputStats, ObsError> {
+        ObsOutputStats::collect(self.as_ptr(), self.runtime())
+    }
+
+    /// Starts collecting the statistics of this output every `interval` on a background thread.
+    /// Unlike `get_stats`, the collected statistics include the current bitrate.
+    ///
+    /// The sampler keeps this output alive and is stopped when it is dropped.
+    #[cfg(feature = "enable_runtime")]
+    fn start_stats_sampler(&self, interval: Duration) -> Result<ObsOutputStatsSampler, ObsError> {
+        ObsOutputStatsSampler::new(self.as_ptr(), self.runtime().clone(), interval)
+    }
+
+    /// Restarts this output according to the given policy whenever it stops unexpectedly.
+    /// This is meant for outputs that can't reconnect on their own, see `set_reconnect_settings`.
+    ///
+    /// The watcher keeps this output alive and is stopped when it is dropped.
+    #[cfg(feature = "enable_runtime")]
+    fn start_reconnect_policy(
+        &self,
+        policy: ObsReconnectPolicy,
+    ) -> Result<ObsReconnectWatcher, ObsError>
+    where
+        Self: Clone + 'static,
+    {
+        ObsReconnectWatcher::new(self.clone(), policy)
+    }
+
+    /// Starts a queue that sends captions to this output at the time they belong to,
+    /// see `ObsCaptionQueue`.
+    ///
+    /// The queue keeps this output alive and is stopped when it is dropped.
+    #[cfg(feature = "enable_runtime")]
+    fn start_caption_queue(&self) -> Result<ObsCaptionQueue, ObsError>
+    where
+        Self: Clone + 'static,
+    {
+        ObsCaptionQueue::new(self.clone())
+    }
 }
@@ -1,3 +1,4 @@
+use bitflags::bitflags;
 use num_derive::{FromPrimitive, ToPrimitive};
 
 #[cfg_attr(target_os = "windows", repr(i32))]
@@ -102,3 +103,14 @@ pub enum ObsButtonType {
     Default = libobs::obs_button_type_OBS_BUTTON_DEFAULT,
     Url = libobs::obs_button_type_OBS_BUTTON_URL,
 }
+
+bitflags! {
+    /// The style flags of a font property.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct ObsFontFlags: u32 {
+        const BOLD = libobs::OBS_FONT_BOLD;
+        const ITALIC = libobs::OBS_FONT_ITALIC;
+        const UNDERLINE = libobs::OBS_FONT_UNDERLINE;
+        const STRIKEOUT = libobs::OBS_FONT_STRIKEOUT;
+    }
+}
@@ -21,38 +21,6 @@ macro_rules! unsafe_is_of_type_result {
     }};
 }
 
-macro_rules! impl_general_property {
-    ($type: ident) => {
-        paste::paste! {
-            #[derive(Debug, getters0::Getters, Clone)]
-            #[skip_new]
-            pub struct [<Obs $type Property>] {
-                name: String,
-                description: Option<String>
-            }
-            impl TryFrom<crate::data::properties::PropertyCreationInfo> for [<Obs $type Property>] {
-                type Error = crate::utils::ObsError;
-
-                fn try_from(
-                    crate::data::properties::PropertyCreationInfo {
-                        name,
-                        description,
-                        pointer,
-                        runtime
-                    }: crate::data::properties::PropertyCreationInfo,
-                ) -> Result<Self, Self::Error> {
-                    crate::run_with_obs!(runtime, (pointer), move || {
-                        crate::data::properties::unsafe_is_of_type_result!($type, pointer)?;
-                        Ok(())
-                    })??;
-
-                    Ok(Self { name, description })
-                }
-            }
-        }
-    };
-}
-
 macro_rules! get_enum {
     ($pointer_name: ident, $name: ident, $enum_name: ident) => {
         paste::paste! {
@@ -100,5 +68,4 @@ macro_rules! get_opt_str {
 
 pub(super) use get_enum;
 pub(super) use get_opt_str;
-pub(super) use impl_general_property;
 pub(super) use unsafe_is_of_type_result;
@@ -7,17 +7,26 @@
 mod enums;
 mod macros;
 pub mod prop_impl;
+#[cfg(feature = "json_schema")]
+mod schema;
+mod session;
+mod tree;
 pub mod types;
+mod validation;
 
-use std::{collections::HashMap, ffi::CStr};
+use std::collections::HashMap;
 
 use libobs::obs_properties;
 use macros::*;
 
 pub use enums::*;
+pub use session::*;
+pub use tree::*;
 use types::*;
+pub use validation::*;
 
 use crate::{
+    data::{ImmutableObsData, ObsData, ObsDataGetters},
     impl_obs_drop, run_with_obs,
     runtime::ObsRuntime,
     unsafe_send::{Sendable, SmartPointerSendable},
@@ -86,77 +95,70 @@ pub trait ObsPropertyObjectPrivate {
         id: T,
         runtime: ObsRuntime,
     ) -> Result<SmartPointerSendable<*mut libobs::obs_properties_t>, ObsError>;
+    fn get_defaults_by_id_raw<T: Into<ObsString> + Sync + Send>(
+        id: T,
+        runtime: ObsRuntime,
+    ) -> Result<ImmutableObsData, ObsError>;
 }
 
 pub(crate) fn property_ptr_to_struct(
     properties_raw: SmartPointerSendable<*mut obs_properties>,
     runtime: ObsRuntime,
 ) -> Result<HashMap<String, ObsProperty>, ObsError> {
+    let tree = property_ptr_to_tree(properties_raw, runtime)?;
+
+    Ok(tree
+        .into_iter()
+        .map(|node| (node.name().clone(), node.property().clone()))
+        .collect())
+}
+
+pub(crate) fn property_ptr_to_tree(
+    properties_raw: SmartPointerSendable<*mut obs_properties>,
+    runtime: ObsRuntime,
+) -> Result<ObsPropertyTree, ObsError> {
     let runtime_clone = runtime.clone();
     run_with_obs!(runtime, (properties_raw, runtime_clone), move || {
-        let mut result = HashMap::new();
-        let mut property = unsafe {
-            // Safety: Safe because of smart pointer
-            libobs::obs_properties_first(properties_raw.get_ptr())
+        let nodes = unsafe {
+            // Safety: Safe because of smart pointer and we are on the OBS thread
+            collect_properties(&runtime_clone, properties_raw.get_ptr())
         };
-        while !property.is_null() {
-            let name = unsafe { libobs::obs_property_name(property) };
-            if name.is_null() {
-                let success = unsafe {
-                    // Safety: Safe because property is not null and we are just moving forward.
-                    libobs::obs_property_next(&mut property)
-                };
-
-                if !success {
-                    break;
-                }
-                continue;
-            }
-
-            let name = unsafe {
-                // Safety: Safe because of we did a null check
-                CStr::from_ptr(name as _)
-            };
-            let name = name.to_string_lossy().to_string();
-
-            let p_type = unsafe {
-                // Safety: Safe because we just got the property pointer
-                libobs::obs_property_get_type(property)
-            };
-
-            let p_type = crate::macros::enum_from_number!(ObsPropertyType, p_type);
-
-            log::trace!("Property: {:?}", name);
-            match p_type {
-                Some(p_type) => {
-                    let prop_struct = unsafe {
-                        // Safety: Safe because we just got the property pointer
-                        p_type.get_property_struct(&runtime_clone, Sendable(property))
-                    };
-                    if let Ok(r) = prop_struct {
-                        result.insert(name, r);
-                    }
-                }
-                None => {
-                    result.insert(name, ObsProperty::Invalid);
-                }
-            }
-
-            // Move to the next property
-            let has_next = unsafe {
-                // Safety: We didn't drop the property, so it is still valid and we can proceed
-                libobs::obs_property_next(&mut property)
-            };
-
-            if !has_next {
-                break;
-            }
-        }
 
-        result
+        ObsPropertyTree::from(nodes)
     })
 }
 
+pub(crate) fn click_button_property<T: 'static>(
+    properties_raw: SmartPointerSendable<*mut obs_properties>,
+    object: SmartPointerSendable<*mut T>,
+    name: ObsString,
+    runtime: ObsRuntime,
+) -> Result<bool, ObsError> {
+    run_with_obs!(runtime, (properties_raw, object, name), move || {
+        let property = unsafe {
+            // Safety: Safe because of smart pointer and the name is owned by this closure.
+            libobs::obs_properties_get(properties_raw.get_ptr(), name.as_ptr().0)
+        };
+
+        if property.is_null() {
+            return Err(ObsError::InvalidOperation(format!(
+                "Property {} does not exist",
+                name
+            )));
+        }
+
+        let property = Sendable(property);
+        unsafe_is_of_type_result!(Button, property)?;
+
+        let needs_refresh = unsafe {
+            // Safety: The property belongs to the properties of the object, which are both kept alive by smart pointers.
+            libobs::obs_property_button_clicked(property.0, object.get_ptr() as *mut _)
+        };
+
+        Ok(needs_refresh)
+    })?
+}
+
 /// This trait is implemented for all obs objects that can have properties
 pub trait ObsPropertyObject: ObsPropertyObjectPrivate {
     /// Returns the properties of the object
@@ -168,4 +170,85 @@ pub trait ObsPropertyObject: ObsPropertyObjectPrivate {
         let properties_raw = Self::get_properties_by_id_raw(id, runtime.clone())?;
         property_ptr_to_struct(properties_raw, runtime.clone())
     }
+
+    /// Returns the properties of the object as an ordered tree, including the content of groups.
+    fn get_properties_tree(&self) -> Result<ObsPropertyTree, ObsError>;
+    fn get_properties_tree_by_source_id<T: Into<ObsString> + Sync + Send>(
+        id: T,
+        runtime: &ObsRuntime,
+    ) -> Result<ObsPropertyTree, ObsError> {
+        let properties_raw = Self::get_properties_by_id_raw(id, runtime.clone())?;
+        property_ptr_to_tree(properties_raw, runtime.clone())
+    }
+
+    /// Creates a session which keeps the properties of this object alive together with the given settings.
+    /// Values can then be applied one by one, running the modified callbacks of the properties.
+    fn get_properties_session(&self, settings: ObsData) -> Result<ObsPropertiesSession, ObsError> {
+        let properties_raw = self.get_properties_raw()?;
+        let runtime = settings.runtime.clone();
+        ObsPropertiesSession::new(properties_raw, settings, runtime)
+    }
+
+    fn get_properties_session_by_source_id<T: Into<ObsString> + Sync + Send>(
+        id: T,
+        settings: ObsData,
+        runtime: &ObsRuntime,
+    ) -> Result<ObsPropertiesSession, ObsError> {
+        let properties_raw = Self::get_properties_by_id_raw(id, runtime.clone())?;
+        ObsPropertiesSession::new(properties_raw, settings, runtime.clone())
+    }
+
+    /// Checks the given settings against the properties of this object, so invalid values can be
+    /// caught before the settings are applied.
+    ///
+    /// The settings are applied to the properties first, so properties that are shown, hidden,
+    /// enabled or disabled by other settings are checked in the state the settings put them in.
+    fn validate_settings<D: ObsDataGetters>(
+        &self,
+        settings: &D,
+    ) -> Result<ObsValidationReport, ObsError> {
+        let properties_raw = self.get_properties_raw()?;
+        validate_with_properties(properties_raw, settings, settings.runtime())
+    }
+
+    /// Checks the given settings against the properties of the object type with the given id.
+    fn validate_settings_by_source_id<T: Into<ObsString> + Sync + Send, D: ObsDataGetters>(
+        id: T,
+        settings: &D,
+        runtime: &ObsRuntime,
+    ) -> Result<ObsValidationReport, ObsError> {
+        let properties_raw = Self::get_properties_by_id_raw(id, runtime.clone())?;
+        validate_with_properties(properties_raw, settings, runtime)
+    }
+
+    /// Returns the default settings of the object type with the given id.
+    fn get_defaults_by_source_id<T: Into<ObsString> + Sync + Send>(
+        id: T,
+        runtime: &ObsRuntime,
+    ) -> Result<ImmutableObsData, ObsError> {
+        Self::get_defaults_by_id_raw(id, runtime.clone())
+    }
+
+    /// Returns a JSON Schema document describing the settings of the object type with the given id,
+    /// including the default value of every property.
+    #[cfg(feature = "json_schema")]
+    fn get_properties_json_schema_by_source_id<T: Into<ObsString> + Sync + Send>(
+        id: T,
+        runtime: &ObsRuntime,
+    ) -> Result<serde_json::Value, ObsError> {
+        let id: ObsString = id.into();
+        let tree = Self::get_properties_tree_by_source_id(id.clone(), runtime)?;
+        let defaults = Self::get_defaults_by_id_raw(id, runtime.clone())?;
+
+        tree.to_json_schema_with_defaults(&defaults)
+    }
+}
+
+/// This trait is implemented for obs objects whose button properties can be clicked.
+/// Clicking a button requires an actual instance of the object, so this is not available
+/// for objects that only have properties by their id (like encoder builders).
+pub trait ObsPropertyButtonExt: ObsPropertyObject {
+    /// Clicks the button property with the given name, running its callback against this object.
+    /// Returns `true` if the properties have changed and should be fetched again.
+    fn click_button<T: Into<ObsString> + Sync + Send>(&self, name: T) -> Result<bool, ObsError>;
 }
@@ -1,7 +1,10 @@
 use std::{collections::HashMap, sync::Arc};
 
 use crate::{
-    data::{object::ObsObjectTrait, output::ObsOutputRef, properties::_ObsPropertiesDropGuard},
+    data::{
+        object::ObsObjectTrait, output::ObsOutputRef, properties::_ObsPropertiesDropGuard,
+        ImmutableObsData,
+    },
     run_with_obs,
     runtime::ObsRuntime,
     sources::ObsSourceTrait,
@@ -9,13 +12,33 @@ use crate::{
     utils::{ObsError, ObsString},
 };
 
-use super::{property_ptr_to_struct, ObsProperty, ObsPropertyObject, ObsPropertyObjectPrivate};
+use super::{
+    click_button_property, property_ptr_to_struct, property_ptr_to_tree, ObsProperty,
+    ObsPropertyButtonExt, ObsPropertyObject, ObsPropertyObjectPrivate, ObsPropertyTree,
+};
 
 impl<K: ObsSourceTrait> ObsPropertyObject for K {
     fn get_properties(&self) -> Result<HashMap<String, ObsProperty>, ObsError> {
         let properties_raw = self.get_properties_raw()?;
         property_ptr_to_struct(properties_raw, self.runtime().clone())
     }
+
+    fn get_properties_tree(&self) -> Result<ObsPropertyTree, ObsError> {
+        let properties_raw = self.get_properties_raw()?;
+        property_ptr_to_tree(properties_raw, self.runtime().clone())
+    }
+}
+
+impl<K: ObsSourceTrait> ObsPropertyButtonExt for K {
+    fn click_button<T: Into<ObsString> + Sync + Send>(&self, name: T) -> Result<bool, ObsError> {
+        let properties_raw = self.get_properties_raw()?;
+        click_button_property(
+            properties_raw,
+            self.as_ptr(),
+            name.into(),
+            self.runtime().clone(),
+        )
+    }
 }
 
 impl<K: ObsSourceTrait> ObsPropertyObjectPrivate for K {
@@ -74,6 +97,28 @@ impl<K: ObsSourceTrait> ObsPropertyObjectPrivate for K {
         let ptr = SmartPointerSendable::new(raw_ptr.0, Arc::new(drop_guard));
         Ok(ptr)
     }
+
+    fn get_defaults_by_id_raw<T: Into<ObsString> + Sync + Send>(
+        id: T,
+        runtime: ObsRuntime,
+    ) -> Result<ImmutableObsData, ObsError> {
+        let id: ObsString = id.into();
+        let data_ptr = run_with_obs!(runtime, (id), move || {
+            let id_ptr = id.as_ptr();
+            let data_ptr = unsafe {
+                // Safety: id_ptr is valid because it comes from ObsString
+                libobs::obs_get_source_defaults(id_ptr.0)
+            };
+
+            if data_ptr.is_null() {
+                Err(ObsError::NullPointer(None))
+            } else {
+                Ok(Sendable(data_ptr))
+            }
+        })??;
+
+        Ok(ImmutableObsData::from_raw_pointer(data_ptr, runtime))
+    }
 }
 
 impl ObsPropertyObject for ObsOutputRef {
@@ -81,6 +126,23 @@ impl ObsPropertyObject for ObsOutputRef {
         let properties_raw = self.get_properties_raw()?;
         property_ptr_to_struct(properties_raw, self.runtime().clone())
     }
+
+    fn get_properties_tree(&self) -> Result<ObsPropertyTree, ObsError> {
+        let properties_raw = self.get_properties_raw()?;
+        property_ptr_to_tree(properties_raw, self.runtime().clone())
+    }
+}
+
+impl ObsPropertyButtonExt for ObsOutputRef {
+    fn click_button<T: Into<ObsString> + Sync + Send>(&self, name: T) -> Result<bool, ObsError> {
+        let properties_raw = self.get_properties_raw()?;
+        click_button_property(
+            properties_raw,
+            self.as_ptr().clone(),
+            name.into(),
+            self.runtime().clone(),
+        )
+    }
 }
 
 impl ObsPropertyObjectPrivate for ObsOutputRef {
@@ -136,4 +198,26 @@ impl ObsPropertyObjectPrivate for ObsOutputRef {
         let ptr = SmartPointerSendable::new(ptr.0, Arc::new(drop_guard));
         Ok(ptr)
     }
+
+    fn get_defaults_by_id_raw<T: Into<ObsString> + Sync + Send>(
+        id: T,
+        runtime: ObsRuntime,
+    ) -> Result<ImmutableObsData, ObsError> {
+        let id: ObsString = id.into();
+        let data_ptr = run_with_obs!(runtime, (id), move || {
+            let id_ptr = id.as_ptr();
+            let data_ptr = unsafe {
+                // Safety: id_ptr is valid because it comes from ObsString
+                libobs::obs_output_defaults(id_ptr.0)
+            };
+
+            if data_ptr.is_null() {
+                Err(ObsError::NullPointer(None))
+            } else {
+                Ok(Sendable(data_ptr))
+            }
+        })??;
+
+        Ok(ImmutableObsData::from_raw_pointer(data_ptr, runtime))
+    }
 }
@@ -0,0 +1,266 @@
+//! Converts properties into a JSON Schema document, so frontends can generate forms for
+//! any source, encoder or output without knowing its settings beforehand.
+
+use std::ffi::CStr;
+
+use serde_json::{json, Map, Value};
+
+use crate::{data::ObsDataPointers, run_with_obs, utils::ObsError};
+
+use super::{
+    types::{ObsListItemValue, ObsListProperty},
+    ObsComboFormat, ObsComboType, ObsGroupType, ObsProperty, ObsPropertyNode, ObsPropertyTree,
+    ObsTextType,
+};
+
+const JSON_SCHEMA_DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+impl ObsPropertyTree {
+    /// Converts this tree into a JSON Schema document describing the settings of the object.
+    ///
+    /// OBS stores the settings of grouped properties in the same object as every other setting,
+    /// so the content of groups is flattened into the top-level `properties` of the schema.
+    /// Buttons are skipped, as they do not store any value.
+    pub fn to_json_schema(&self) -> Value {
+        build_schema(self, &Map::new())
+    }
+
+    /// Same as `to_json_schema`, but also fills in the `default` keyword of every property
+    /// using the given data, for example the defaults of a source type.
+    pub fn to_json_schema_with_defaults<D: ObsDataPointers>(
+        &self,
+        defaults: &D,
+    ) -> Result<Value, ObsError> {
+        let data_ptr = defaults.as_ptr();
+        let json = run_with_obs!(defaults.runtime(), (data_ptr), move || {
+            let json_ptr = unsafe {
+                // Safety: The pointer is valid because we are using a smart pointer
+                libobs::obs_data_get_json_with_defaults(data_ptr.get_ptr())
+            };
+
+            if json_ptr.is_null() {
+                return Err(ObsError::NullPointer(Some(
+                    "Couldn't get json representation of OBS data".into(),
+                )));
+            }
+
+            let json = unsafe {
+                // Safety: The pointer is valid because OBS returned it and we are still in runtime.
+                CStr::from_ptr(json_ptr)
+            }
+            .to_str()
+            .map_err(|_| ObsError::JsonParseError)?
+            .to_string();
+
+            Ok(json)
+        })??;
+
+        let defaults = match serde_json::from_str(&json) {
+            Ok(Value::Object(map)) => map,
+            _ => return Err(ObsError::JsonParseError),
+        };
+
+        Ok(build_schema(self, &defaults))
+    }
+}
+
+fn build_schema(tree: &ObsPropertyTree, defaults: &Map<String, Value>) -> Value {
+    let mut properties = Map::new();
+    add_nodes(tree, defaults, &mut properties);
+
+    json!({
+        "$schema": JSON_SCHEMA_DRAFT,
+        "type": "object",
+        "properties": properties,
+    })
+}
+
+fn add_nodes(
+    tree: &ObsPropertyTree,
+    defaults: &Map<String, Value>,
+    properties: &mut Map<String, Value>,
+) {
+    for node in tree {
+        if let Some(mut schema) = node_schema(node) {
+            if let (Value::Object(schema), Some(default)) = (&mut schema, defaults.get(node.name()))
+            {
+                schema.insert("default".to_string(), default.clone());
+            }
+
+            properties.insert(node.name().clone(), schema);
+        }
+
+        if let Some(children) = node.children() {
+            add_nodes(children, defaults, properties);
+        }
+    }
+}
+
+fn with_description(mut schema: Value, description: &Option<String>) -> Value {
+    if let (Value::Object(map), Some(description)) = (&mut schema, description) {
+        map.insert("title".to_string(), Value::String(description.clone()));
+    }
+
+    schema
+}
+
+fn node_schema(node: &ObsPropertyNode) -> Option<Value> {
+    let schema = match node.property() {
+        ObsProperty::Invalid | ObsProperty::Button(_) => return None,
+        ObsProperty::Bool => json!({ "type": "boolean" }),
+        ObsProperty::Int(p) => {
+            let mut schema = json!({
+                "type": "integer",
+                "minimum": p.min(),
+                "maximum": p.max(),
+            });
+            // OBS counts the steps from the minimum, which `multipleOf` can only
+            // express if the minimum itself is on a step
+            if *p.step() > 1 && p.min().rem_euclid(*p.step()) == 0 {
+                schema["multipleOf"] = Value::from(*p.step());
+            }
+
+            with_description(schema, p.description())
+        }
+        ObsProperty::Float(p) => with_description(
+            json!({
+                "type": "number",
+                "minimum": p.min(),
+                "maximum": p.max(),
+            }),
+            p.description(),
+        ),
+        ObsProperty::Text(p) => {
+            let mut schema = json!({ "type": "string" });
+            if *p.text_type() == ObsTextType::Info {
+                schema["readOnly"] = Value::Bool(true);
+            }
+
+            with_description(schema, p.description())
+        }
+        ObsProperty::Path(p) => with_description(
+            json!({
+                "type": "string",
+                "format": "path",
+            }),
+            p.description(),
+        ),
+        ObsProperty::List(p) => with_description(list_schema(p), p.description()),
+        ObsProperty::Color(p) => with_description(
+            json!({
+                "type": "integer",
+                "format": "color",
+                "minimum": 0,
+                "maximum": u32::MAX,
+            }),
+            p.description(),
+        ),
+        ObsProperty::ColorAlpha(p) => with_description(
+            json!({
+                "type": "integer",
+                "format": "color-alpha",
+                "minimum": 0,
+                "maximum": u32::MAX,
+            }),
+            p.description(),
+        ),
+        ObsProperty::Font(p) => with_description(
+            json!({
+                "type": "object",
+                "properties": {
+                    "face": { "type": "string" },
+                    "style": { "type": "string" },
+                    "size": { "type": "integer", "minimum": 0 },
+                    "flags": { "type": "integer", "minimum": 0 },
+                },
+            }),
+            p.description(),
+        ),
+        ObsProperty::EditableList(p) => with_description(
+            json!({
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "value": { "type": "string" },
+                        "selected": { "type": "boolean" },
+                        "hidden": { "type": "boolean" },
+                    },
+                },
+            }),
+            p.description(),
+        ),
+        ObsProperty::FrameRate(p) => {
+            let options: Vec<&String> = p.options().iter().map(|o| o.name()).collect();
+            with_description(
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "option": { "type": "string", "enum": options },
+                        "numerator": { "type": "integer", "minimum": 0 },
+                        "denominator": { "type": "integer", "minimum": 0 },
+                    },
+                }),
+                p.description(),
+            )
+        }
+        ObsProperty::Group(p) => {
+            // Only checkable groups store a value, which is the state of their checkbox
+            if *p.group_type() != ObsGroupType::Checkable {
+                return None;
+            }
+
+            with_description(json!({ "type": "boolean" }), p.description())
+        }
+    };
+
+    Some(schema)
+}
+
+fn list_schema(list: &ObsListProperty) -> Value {
+    let value_type = match list.format() {
+        ObsComboFormat::Int => "integer",
+        ObsComboFormat::Float => "number",
+        ObsComboFormat::Bool => "boolean",
+        ObsComboFormat::String | ObsComboFormat::Invalid => "string",
+    };
+
+    // Disabled items can't be selected in OBS, so they are not offered
+    let items: Vec<(Value, &String)> = list
+        .items()
+        .iter()
+        .filter(|item| !item.disabled())
+        .filter_map(|item| {
+            let value = match item.value() {
+                ObsListItemValue::String(s) => Value::from(s.clone()),
+                ObsListItemValue::Int(i) => Value::from(*i),
+                ObsListItemValue::Float(f) => Value::from(*f),
+                ObsListItemValue::Bool(b) => Value::from(*b),
+                ObsListItemValue::Invalid => return None,
+            };
+
+            Some((value, item.name()))
+        })
+        .collect();
+
+    let mut schema = json!({ "type": value_type });
+    if items.is_empty() {
+        return schema;
+    }
+
+    // Editable combo boxes accept any value, so the items are only suggestions
+    if *list.list_type() == ObsComboType::Editable {
+        let examples: Vec<&Value> = items.iter().map(|(value, _)| value).collect();
+        schema["examples"] = json!(examples);
+        return schema;
+    }
+
+    // Lists can contain the same value more than once, which `anyOf` accepts
+    let items: Vec<Value> = items
+        .into_iter()
+        .map(|(value, name)| json!({ "const": value, "title": name }))
+        .collect();
+    schema["anyOf"] = Value::Array(items);
+
+    schema
+}
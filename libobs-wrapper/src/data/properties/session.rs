@@ -0,0 +1,175 @@
+use libobs::obs_properties;
+
+use crate::{
+    data::{ObsData, ObsDataPointers},
+    run_with_obs,
+    runtime::ObsRuntime,
+    unsafe_send::SmartPointerSendable,
+    utils::{ObsError, ObsString},
+};
+
+use super::{collect_properties, ObsPropertyTree};
+
+/// A value that can be applied to a property in an `ObsPropertiesSession`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObsPropertyValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+impl From<bool> for ObsPropertyValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for ObsPropertyValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<f64> for ObsPropertyValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<String> for ObsPropertyValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for ObsPropertyValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+/// Keeps the properties of an object alive together with the settings they are applied to.
+///
+/// Properties in OBS are dynamic, so changing one value (for example the rate control of an encoder)
+/// runs the modified callback of that property, which can show, hide, enable or disable other
+/// properties or refill their list items. This session lets you apply values one at a time and
+/// returns the updated state of every property after each change.
+#[derive(Debug)]
+pub struct ObsPropertiesSession {
+    properties: SmartPointerSendable<*mut obs_properties>,
+    settings: ObsData,
+    runtime: ObsRuntime,
+}
+
+impl ObsPropertiesSession {
+    /// Creates a new session and applies the given settings to the properties,
+    /// so the visible and enabled states match the settings.
+    pub(crate) fn new(
+        properties: SmartPointerSendable<*mut obs_properties>,
+        settings: ObsData,
+        runtime: ObsRuntime,
+    ) -> Result<Self, ObsError> {
+        let settings_ptr = settings.as_ptr();
+        run_with_obs!(runtime, (properties, settings_ptr), move || unsafe {
+            // Safety: Both pointers are valid because they are smart pointers and we are on the OBS thread.
+            libobs::obs_properties_apply_settings(properties.get_ptr(), settings_ptr.get_ptr());
+        })?;
+
+        Ok(Self {
+            properties,
+            settings,
+            runtime,
+        })
+    }
+
+    /// Returns the settings that are modified by this session.
+    pub fn settings(&self) -> &ObsData {
+        &self.settings
+    }
+
+    /// Consumes the session and returns the modified settings, which can then be
+    /// used to update the object the properties belong to.
+    pub fn into_settings(self) -> ObsData {
+        self.settings
+    }
+
+    /// Returns the current state of all properties without changing any value.
+    pub fn properties(&self) -> Result<ObsPropertyTree, ObsError> {
+        let properties = self.properties.clone();
+        let runtime = self.runtime.clone();
+        run_with_obs!(self.runtime, (properties, runtime), move || {
+            let nodes = unsafe {
+                // Safety: Safe because of smart pointer and we are on the OBS thread
+                collect_properties(&runtime, properties.get_ptr())
+            };
+
+            ObsPropertyTree::from(nodes)
+        })
+    }
+
+    /// Sets the value of the property with the given name in the settings and runs its modified callback.
+    /// Properties within groups can be modified as well.
+    ///
+    /// Returns the updated state of all properties, including their visibility, whether they are enabled
+    /// and refreshed list items.
+    pub fn apply<T: Into<ObsString>, V: Into<ObsPropertyValue>>(
+        &mut self,
+        name: T,
+        value: V,
+    ) -> Result<ObsPropertyTree, ObsError> {
+        let name: ObsString = name.into();
+        let value: ObsPropertyValue = value.into();
+
+        let properties = self.properties.clone();
+        let settings_ptr = self.settings.as_ptr();
+        let runtime = self.runtime.clone();
+        run_with_obs!(
+            self.runtime,
+            (properties, settings_ptr, runtime, name),
+            move || {
+                let property = unsafe {
+                    // Safety: Safe because of smart pointer and the name is owned by this closure.
+                    libobs::obs_properties_get(properties.get_ptr(), name.as_ptr().0)
+                };
+
+                if property.is_null() {
+                    return Err(ObsError::InvalidOperation(format!(
+                        "Property {} does not exist",
+                        name
+                    )));
+                }
+
+                unsafe {
+                    // Safety: The settings pointer is valid because of smart pointer and all strings are owned by this closure.
+                    let data = settings_ptr.get_ptr();
+                    match value {
+                        ObsPropertyValue::Bool(v) => {
+                            libobs::obs_data_set_bool(data, name.as_ptr().0, v)
+                        }
+                        ObsPropertyValue::Int(v) => {
+                            libobs::obs_data_set_int(data, name.as_ptr().0, v)
+                        }
+                        ObsPropertyValue::Float(v) => {
+                            libobs::obs_data_set_double(data, name.as_ptr().0, v)
+                        }
+                        ObsPropertyValue::String(v) => {
+                            let v = ObsString::new(v);
+                            libobs::obs_data_set_string(data, name.as_ptr().0, v.as_ptr().0)
+                        }
+                    }
+
+                    // Safety: The property belongs to the properties object, which is still alive.
+                    libobs::obs_property_modified(property, data);
+                }
+
+                let nodes = unsafe {
+                    // Safety: Safe because of smart pointer and we are on the OBS thread
+                    collect_properties(&runtime, properties.get_ptr())
+                };
+
+                Ok(ObsPropertyTree::from(nodes))
+            }
+        )?
+    }
+}
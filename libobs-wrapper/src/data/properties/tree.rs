@@ -0,0 +1,184 @@
+use std::ffi::CStr;
+
+use getters0::Getters;
+use libobs::obs_properties;
+
+use crate::{runtime::ObsRuntime, unsafe_send::Sendable};
+
+use super::{ObsListItem, ObsProperty, ObsPropertyType};
+
+/// A single property within an `ObsPropertyTree`.
+#[derive(Debug, Getters, Clone)]
+#[skip_new]
+pub struct ObsPropertyNode {
+    name: String,
+    property: ObsProperty,
+    /// Whether the property is currently visible. This might change when other settings are modified.
+    visible: bool,
+    /// Whether the property is currently enabled. This might change when other settings are modified.
+    enabled: bool,
+}
+
+impl ObsPropertyNode {
+    pub(crate) fn new(name: String, property: ObsProperty, visible: bool, enabled: bool) -> Self {
+        Self {
+            name,
+            property,
+            visible,
+            enabled,
+        }
+    }
+
+    /// Returns the child properties if this node is a group.
+    pub fn children(&self) -> Option<&ObsPropertyTree> {
+        match &self.property {
+            ObsProperty::Group(group) => Some(group.content()),
+            _ => None,
+        }
+    }
+
+    /// Returns the items of this property if it is a list.
+    pub fn list_items(&self) -> Option<&[ObsListItem]> {
+        match &self.property {
+            ObsProperty::List(list) => Some(list.items()),
+            _ => None,
+        }
+    }
+}
+
+/// Holds the properties of an object in the order OBS declares them.
+/// Groups are not flattened, their content can be accessed by using `ObsPropertyNode::children`.
+#[derive(Debug, Clone, Default)]
+pub struct ObsPropertyTree {
+    nodes: Vec<ObsPropertyNode>,
+}
+
+impl ObsPropertyTree {
+    /// Returns the top-level properties of this tree.
+    pub fn nodes(&self) -> &[ObsPropertyNode] {
+        &self.nodes
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, ObsPropertyNode> {
+        self.nodes.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Searches for a property with the given name, descending into groups.
+    pub fn find(&self, name: &str) -> Option<&ObsPropertyNode> {
+        self.nodes.iter().find_map(|node| {
+            if node.name == name {
+                return Some(node);
+            }
+
+            node.children().and_then(|children| children.find(name))
+        })
+    }
+
+    /// Returns every property of this tree depth-first.
+    /// Groups are returned before their children.
+    pub fn flatten(&self) -> Vec<&ObsPropertyNode> {
+        let mut result = Vec::new();
+        for node in &self.nodes {
+            result.push(node);
+            if let Some(children) = node.children() {
+                result.extend(children.flatten());
+            }
+        }
+
+        result
+    }
+}
+
+impl From<Vec<ObsPropertyNode>> for ObsPropertyTree {
+    fn from(nodes: Vec<ObsPropertyNode>) -> Self {
+        Self { nodes }
+    }
+}
+
+impl IntoIterator for ObsPropertyTree {
+    type Item = ObsPropertyNode;
+    type IntoIter = std::vec::IntoIter<ObsPropertyNode>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ObsPropertyTree {
+    type Item = &'a ObsPropertyNode;
+    type IntoIter = std::slice::Iter<'a, ObsPropertyNode>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.iter()
+    }
+}
+
+/// Iterates over the given properties and converts each one into its struct representation,
+/// keeping the order OBS declared them in. Properties that fail to convert are skipped.
+///
+/// # Safety
+/// This function must be called on the OBS runtime and `properties` must be a valid pointer.
+#[allow(unknown_lints)]
+#[allow(ensure_obs_call_in_runtime)]
+pub(crate) unsafe fn collect_properties(
+    runtime: &ObsRuntime,
+    properties: *mut obs_properties,
+) -> Vec<ObsPropertyNode> {
+    let mut result = Vec::new();
+    let mut property = libobs::obs_properties_first(properties);
+    while !property.is_null() {
+        let name = libobs::obs_property_name(property);
+        if name.is_null() {
+            // Safety: Safe because property is not null and we are just moving forward.
+            if !libobs::obs_property_next(&mut property) {
+                break;
+            }
+            continue;
+        }
+
+        // Safety: Safe because of we did a null check
+        let name = CStr::from_ptr(name as _).to_string_lossy().to_string();
+
+        // Safety: Safe because we just got the property pointer
+        let p_type = libobs::obs_property_get_type(property);
+        let p_type = crate::macros::enum_from_number!(ObsPropertyType, p_type);
+
+        // Safety: Safe because we just got the property pointer
+        let visible = libobs::obs_property_visible(property);
+        let enabled = libobs::obs_property_enabled(property);
+
+        log::trace!("Property: {:?}", name);
+        match p_type {
+            Some(p_type) => {
+                // Safety: Safe because we just got the property pointer
+                let prop_struct = p_type.get_property_struct(runtime, Sendable(property));
+                if let Ok(r) = prop_struct {
+                    result.push(ObsPropertyNode::new(name, r, visible, enabled));
+                }
+            }
+            None => {
+                result.push(ObsPropertyNode::new(
+                    name,
+                    ObsProperty::Invalid,
+                    visible,
+                    enabled,
+                ));
+            }
+        }
+
+        // Safety: We didn't drop the property, so it is still valid and we can proceed
+        if !libobs::obs_property_next(&mut property) {
+            break;
+        }
+    }
+
+    result
+}
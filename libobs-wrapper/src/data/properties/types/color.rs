@@ -0,0 +1,148 @@
+use getters0::Getters;
+
+use crate::{
+    data::{properties::unsafe_is_of_type_result, ObsDataGetters, ObsDataSetters},
+    run_with_obs,
+    utils::ObsError,
+};
+
+use super::PropertyCreationInfo;
+
+/// A color as it is stored by OBS in settings.
+/// OBS stores colors as a single integer in the `0xAABBGGRR` format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Getters)]
+pub struct ObsColor {
+    #[get_mut]
+    r: u8,
+    #[get_mut]
+    g: u8,
+    #[get_mut]
+    b: u8,
+    #[get_mut]
+    a: u8,
+}
+
+impl ObsColor {
+    /// Creates a fully opaque color.
+    pub fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 255 }
+    }
+
+    /// Converts the integer representation OBS uses (`0xAABBGGRR`) into a color.
+    pub fn from_obs_int(value: i64) -> Self {
+        let value = value as u32;
+        Self {
+            r: (value & 0xFF) as u8,
+            g: ((value >> 8) & 0xFF) as u8,
+            b: ((value >> 16) & 0xFF) as u8,
+            a: ((value >> 24) & 0xFF) as u8,
+        }
+    }
+
+    /// Converts this color into the integer representation OBS uses (`0xAABBGGRR`).
+    pub fn to_obs_int(&self) -> i64 {
+        let value = (self.r as u32)
+            | ((self.g as u32) << 8)
+            | ((self.b as u32) << 16)
+            | ((self.a as u32) << 24);
+
+        value as i64
+    }
+}
+
+/// Color and color alpha properties only differ in whether OBS keeps the alpha channel,
+/// both store the color as an integer under the name of the property.
+macro_rules! impl_color_property {
+    ($type: ident, $has_alpha: literal) => {
+        paste::paste! {
+            #[derive(Debug, Getters, Clone)]
+            #[skip_new]
+            pub struct [<Obs $type Property>] {
+                name: String,
+                description: Option<String>,
+            }
+
+            impl TryFrom<PropertyCreationInfo> for [<Obs $type Property>] {
+                type Error = ObsError;
+
+                fn try_from(
+                    PropertyCreationInfo {
+                        name,
+                        description,
+                        pointer,
+                        runtime,
+                    }: PropertyCreationInfo,
+                ) -> Result<Self, Self::Error> {
+                    run_with_obs!(runtime, (pointer), move || {
+                        unsafe_is_of_type_result!($type, pointer)
+                    })??;
+
+                    Ok(Self { name, description })
+                }
+            }
+
+            impl [<Obs $type Property>] {
+                /// Returns whether OBS keeps the alpha channel of colors set by this property.
+                pub fn has_alpha(&self) -> bool {
+                    $has_alpha
+                }
+
+                /// Returns the color as OBS stores it for this property.
+                pub fn normalize(&self, color: ObsColor) -> ObsColor {
+                    if $has_alpha {
+                        color
+                    } else {
+                        ObsColor { a: 255, ..color }
+                    }
+                }
+
+                /// Gets the color of this property from the settings of its object.
+                pub fn get_color<D: ObsDataGetters>(
+                    &self,
+                    settings: &D,
+                ) -> Result<Option<ObsColor>, ObsError> {
+                    Ok(settings
+                        .get_color(self.name.as_str())?
+                        .map(|color| self.normalize(color)))
+                }
+
+                /// Gets the default color of this property from the settings of its object.
+                pub fn get_default_color<D: ObsDataGetters>(
+                    &self,
+                    settings: &D,
+                ) -> Result<Option<ObsColor>, ObsError> {
+                    Ok(settings
+                        .get_default_color(self.name.as_str())?
+                        .map(|color| self.normalize(color)))
+                }
+
+                /// Sets the color of this property in the given settings, see `normalize`.
+                pub fn set_color<D: ObsDataSetters>(
+                    &self,
+                    settings: &mut D,
+                    color: ObsColor,
+                ) -> Result<(), ObsError> {
+                    settings.set_color(self.name.as_str(), self.normalize(color))?;
+                    Ok(())
+                }
+            }
+        }
+    };
+}
+
+impl_color_property!(Color, false);
+impl_color_property!(ColorAlpha, true);
+
+#[test]
+fn test_color_obs_int_roundtrip() {
+    let color = ObsColor::new(0x11, 0x22, 0x33, 0x44);
+    assert_eq!(color.to_obs_int(), 0x44332211);
+    assert_eq!(ObsColor::from_obs_int(0x44332211), color);
+}
+
+#[test]
+fn test_color_rgb_is_opaque() {
+    let color = ObsColor::rgb(255, 0, 0);
+    assert_eq!(*color.a(), 255);
+    assert_eq!(color.to_obs_int(), 0xFF0000FF);
+}
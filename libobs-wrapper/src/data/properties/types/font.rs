@@ -0,0 +1,79 @@
+use getters0::Getters;
+
+use crate::{
+    data::{
+        properties::{unsafe_is_of_type_result, ObsFontFlags},
+        ObsDataGetters, ObsDataSetters,
+    },
+    run_with_obs,
+    utils::ObsError,
+};
+
+use super::PropertyCreationInfo;
+
+/// A font as it is stored by OBS for font properties.
+/// OBS stores fonts as a sub-object with the `face`, `style`, `size` and `flags` keys.
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+pub struct ObsFont {
+    #[get_mut]
+    face: String,
+    #[get_mut]
+    style: String,
+    #[get_mut]
+    size: i64,
+    #[get_mut]
+    flags: ObsFontFlags,
+}
+
+#[derive(Debug, Getters, Clone)]
+#[skip_new]
+pub struct ObsFontProperty {
+    name: String,
+    description: Option<String>,
+}
+
+impl TryFrom<PropertyCreationInfo> for ObsFontProperty {
+    type Error = ObsError;
+
+    fn try_from(
+        PropertyCreationInfo {
+            name,
+            description,
+            pointer,
+            runtime,
+        }: PropertyCreationInfo,
+    ) -> Result<Self, Self::Error> {
+        run_with_obs!(runtime, (pointer), move || {
+            unsafe_is_of_type_result!(Font, pointer)
+        })??;
+
+        Ok(Self { name, description })
+    }
+}
+
+impl ObsFontProperty {
+    /// Gets the font of this property from the settings of its object,
+    /// or the default font if none was set.
+    pub fn get_font<D: ObsDataGetters>(&self, settings: &D) -> Result<Option<ObsFont>, ObsError> {
+        settings.get_font(self.name.as_str())
+    }
+
+    /// Gets the default font of this property, which the object sets in its defaults.
+    /// `None` if the object doesn't define a default font.
+    pub fn get_default_font<D: ObsDataGetters>(
+        &self,
+        settings: &D,
+    ) -> Result<Option<ObsFont>, ObsError> {
+        settings.get_default_font(self.name.as_str())
+    }
+
+    /// Sets the font of this property in the given settings.
+    pub fn set_font<D: ObsDataSetters>(
+        &self,
+        settings: &mut D,
+        font: &ObsFont,
+    ) -> Result<(), ObsError> {
+        settings.set_font(self.name.as_str(), font)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,195 @@
+use std::ffi::CStr;
+
+use getters0::Getters;
+use libobs::media_frames_per_second;
+
+use crate::{data::properties::unsafe_is_of_type_result, run_with_obs};
+
+use super::PropertyCreationInfo;
+
+/// A frame rate expressed as a fraction, for example `30000 / 1001` for 29.97 fps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Getters)]
+pub struct ObsFrameRate {
+    #[get_mut]
+    numerator: u32,
+    #[get_mut]
+    denominator: u32,
+}
+
+impl ObsFrameRate {
+    /// Returns the frame rate as frames per second, or `None` if the denominator is zero.
+    pub fn as_f64(&self) -> Option<f64> {
+        if self.denominator == 0 {
+            return None;
+        }
+
+        Some(self.numerator as f64 / self.denominator as f64)
+    }
+}
+
+impl From<media_frames_per_second> for ObsFrameRate {
+    fn from(value: media_frames_per_second) -> Self {
+        Self {
+            numerator: value.numerator,
+            denominator: value.denominator,
+        }
+    }
+}
+
+impl From<ObsFrameRate> for media_frames_per_second {
+    fn from(value: ObsFrameRate) -> Self {
+        media_frames_per_second {
+            numerator: value.numerator,
+            denominator: value.denominator,
+        }
+    }
+}
+
+/// The value of a frame rate property. Frame rate properties either store one of
+/// their named options (for example "Match output FPS") or an actual frame rate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObsFrameRateValue {
+    Option(String),
+    FrameRate(ObsFrameRate),
+}
+
+/// A named option of a frame rate property.
+#[derive(Debug, Getters, Clone)]
+#[skip_new]
+pub struct ObsFrameRateOption {
+    name: String,
+    description: Option<String>,
+}
+
+/// A range of frame rates a frame rate property accepts.
+#[derive(Debug, Getters, Clone)]
+#[skip_new]
+pub struct ObsFrameRateRange {
+    min: ObsFrameRate,
+    max: ObsFrameRate,
+}
+
+impl ObsFrameRateRange {
+    /// Checks if the given frame rate lies within this range.
+    pub fn contains(&self, frame_rate: &ObsFrameRate) -> bool {
+        match (frame_rate.as_f64(), self.min.as_f64(), self.max.as_f64()) {
+            (Some(fps), Some(min), Some(max)) => fps >= min && fps <= max,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Getters, Clone)]
+#[skip_new]
+pub struct ObsFrameRateProperty {
+    name: String,
+    description: Option<String>,
+    options: Vec<ObsFrameRateOption>,
+    ranges: Vec<ObsFrameRateRange>,
+}
+
+impl TryFrom<PropertyCreationInfo> for ObsFrameRateProperty {
+    type Error = crate::utils::ObsError;
+
+    fn try_from(
+        PropertyCreationInfo {
+            name,
+            description,
+            pointer,
+            runtime,
+        }: PropertyCreationInfo,
+    ) -> Result<Self, Self::Error> {
+        run_with_obs!(runtime, (pointer), move || {
+            unsafe_is_of_type_result!(FrameRate, pointer)?;
+
+            let option_count = unsafe {
+                // Safety: The caller must have ensured that the pointer is valid
+                libobs::obs_property_frame_rate_options_count(pointer.0)
+            };
+
+            let mut options = Vec::with_capacity(option_count);
+            for i in 0..option_count {
+                let option_name = unsafe {
+                    // Safety: The caller must have ensured that the pointer is valid
+                    libobs::obs_property_frame_rate_option_name(pointer.0, i)
+                };
+
+                if option_name.is_null() {
+                    continue;
+                }
+
+                let option_name = unsafe {
+                    // Safety: Safe because we did a null check
+                    CStr::from_ptr(option_name).to_string_lossy().to_string()
+                };
+
+                let option_description = unsafe {
+                    // Safety: The caller must have ensured that the pointer is valid
+                    libobs::obs_property_frame_rate_option_description(pointer.0, i)
+                };
+
+                let option_description = if option_description.is_null() {
+                    None
+                } else {
+                    Some(unsafe {
+                        // Safety: Safe because we did a null check
+                        CStr::from_ptr(option_description)
+                            .to_string_lossy()
+                            .to_string()
+                    })
+                };
+
+                options.push(ObsFrameRateOption {
+                    name: option_name,
+                    description: option_description,
+                });
+            }
+
+            let range_count = unsafe {
+                // Safety: The caller must have ensured that the pointer is valid
+                libobs::obs_property_frame_rate_fps_ranges_count(pointer.0)
+            };
+
+            let mut ranges = Vec::with_capacity(range_count);
+            for i in 0..range_count {
+                let (min, max) = unsafe {
+                    // Safety: The caller must have ensured that the pointer is valid and i is within bounds
+                    (
+                        libobs::obs_property_frame_rate_fps_range_min(pointer.0, i),
+                        libobs::obs_property_frame_rate_fps_range_max(pointer.0, i),
+                    )
+                };
+
+                ranges.push(ObsFrameRateRange {
+                    min: min.into(),
+                    max: max.into(),
+                });
+            }
+
+            Ok(Self {
+                name,
+                description,
+                options,
+                ranges,
+            })
+        })?
+    }
+}
+
+#[test]
+fn test_frame_rate_as_f64() {
+    let ntsc = ObsFrameRate::new(30000, 1001);
+    assert!((ntsc.as_f64().unwrap() - 29.97).abs() < 0.01);
+    assert_eq!(ObsFrameRate::new(30, 0).as_f64(), None);
+}
+
+#[test]
+fn test_frame_rate_range_contains() {
+    let range = ObsFrameRateRange {
+        min: ObsFrameRate::new(1, 1),
+        max: ObsFrameRate::new(60, 1),
+    };
+
+    assert!(range.contains(&ObsFrameRate::new(30000, 1001)));
+    assert!(!range.contains(&ObsFrameRate::new(120, 1)));
+}
@@ -0,0 +1,65 @@
+use getters0::Getters;
+
+use crate::{
+    data::properties::{
+        collect_properties, get_enum, unsafe_is_of_type_result, ObsGroupType, ObsPropertyTree,
+    },
+    run_with_obs,
+};
+
+use super::PropertyCreationInfo;
+
+/// A group of properties. Groups can either be normal groups, which are just used to
+/// visually structure properties, or checkable groups, which additionally have a boolean
+/// value stored under the name of the group.
+#[derive(Debug, Getters, Clone)]
+#[skip_new]
+pub struct ObsGroupProperty {
+    name: String,
+    description: Option<String>,
+    group_type: ObsGroupType,
+    /// The properties within this group, in the order OBS defines them.
+    content: ObsPropertyTree,
+}
+
+impl TryFrom<PropertyCreationInfo> for ObsGroupProperty {
+    type Error = crate::utils::ObsError;
+
+    fn try_from(
+        PropertyCreationInfo {
+            name,
+            description,
+            pointer,
+            runtime,
+        }: PropertyCreationInfo,
+    ) -> Result<Self, Self::Error> {
+        let runtime_clone = runtime.clone();
+        run_with_obs!(runtime, (pointer, runtime_clone), move || {
+            unsafe_is_of_type_result!(Group, pointer)?;
+
+            let group_type = get_enum!(pointer, group_type, ObsGroupType)?;
+            let content_ptr = unsafe {
+                // Safety: The caller must have ensured that the pointer is valid
+                libobs::obs_property_group_content(pointer.0)
+            };
+
+            let content = if content_ptr.is_null() {
+                ObsPropertyTree::default()
+            } else {
+                let nodes = unsafe {
+                    // Safety: The content is owned by the group property, which is valid and we are on the OBS thread.
+                    collect_properties(&runtime_clone, content_ptr)
+                };
+
+                ObsPropertyTree::from(nodes)
+            };
+
+            Ok(Self {
+                name,
+                description,
+                group_type,
+                content,
+            })
+        })?
+    }
+}
@@ -24,7 +24,7 @@ pub struct ObsListItem {
     disabled: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ObsListItemValue {
     String(String),
     Int(i64),
@@ -2,12 +2,11 @@
 //! All structs in this module use direct obs calls to get the data from the obs_property_t struct. **ALWAYS MAKE SURE THIS IS RUNNING ON THE OBS THREAD**
 
 mod button;
-impl_general_property!(Color);
+mod color;
 mod editable_list;
-impl_general_property!(Font);
-impl_general_property!(FrameRate);
-impl_general_property!(Group);
-impl_general_property!(ColorAlpha);
+mod font;
+mod frame_rate;
+mod group;
 mod list;
 mod number;
 mod path;
@@ -41,7 +40,11 @@ impl PropertyCreationInfo {
 use std::ffi::CStr;
 
 pub use button::*;
+pub use color::*;
 pub use editable_list::*;
+pub use font::*;
+pub use frame_rate::*;
+pub use group::*;
 use libobs::obs_property;
 pub use list::*;
 pub use number::*;
@@ -50,7 +53,7 @@ pub use text::*;
 
 use crate::{run_with_obs, runtime::ObsRuntime, unsafe_send::Sendable, utils::ObsError};
 
-use super::{macros::impl_general_property, ObsProperty, ObsPropertyType};
+use super::{ObsProperty, ObsPropertyType};
 
 impl ObsPropertyType {
     /// Safety:
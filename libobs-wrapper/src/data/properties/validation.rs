@@ -0,0 +1,316 @@
+use std::path::Path;
+
+use getters0::Getters;
+
+use libobs::obs_properties;
+
+use crate::{
+    data::ObsDataGetters, run_with_obs, runtime::ObsRuntime, unsafe_send::SmartPointerSendable,
+    utils::ObsError,
+};
+
+use super::{
+    collect_properties,
+    types::{ObsListItemValue, ObsListProperty, ObsNumberProperty, ObsPathProperty},
+    ObsComboFormat, ObsComboType, ObsPathType, ObsProperty, ObsPropertyTree,
+};
+
+/// Describes why the value of a setting does not match its property definition.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObsValidationIssueKind {
+    /// The value is smaller than the minimum of the number property.
+    BelowMinimum { value: f64, min: f64 },
+    /// The value is larger than the maximum of the number property.
+    AboveMaximum { value: f64, max: f64 },
+    /// The value does not lie on a step of the integer property, counted from its minimum.
+    NotOnStep { value: f64, step: f64 },
+    /// The value is not one of the items of the list property.
+    NotInList { value: ObsListItemValue },
+    /// The value is an item of the list property, but the item is disabled.
+    DisabledListItem { value: ObsListItemValue },
+    /// The path of the path property does not exist.
+    PathNotFound { path: String },
+}
+
+/// A single setting that does not match its property definition.
+#[derive(Debug, Clone, Getters)]
+#[skip_new]
+pub struct ObsValidationIssue {
+    /// The name of the property and therefore the key of the setting
+    key: String,
+    kind: ObsValidationIssueKind,
+}
+
+/// The result of validating settings against the properties of an object.
+#[derive(Debug, Clone, Default)]
+pub struct ObsValidationReport {
+    issues: Vec<ObsValidationIssue>,
+}
+
+impl ObsValidationReport {
+    /// Returns `true` if no issues were found.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn issues(&self) -> &[ObsValidationIssue] {
+        &self.issues
+    }
+
+    /// Returns the issues of the setting with the given key.
+    pub fn issues_for(&self, key: &str) -> Vec<&ObsValidationIssue> {
+        self.issues.iter().filter(|i| i.key == key).collect()
+    }
+
+    fn push(&mut self, key: &str, kinds: Vec<ObsValidationIssueKind>) {
+        self.issues
+            .extend(kinds.into_iter().map(|kind| ObsValidationIssue {
+                key: key.to_string(),
+                kind,
+            }));
+    }
+}
+
+impl ObsPropertyTree {
+    /// Checks the given settings against the properties of this tree, including the content of groups.
+    /// The visible and enabled states of the tree should match the settings, which is the case for
+    /// `ObsPropertyObject::validate_settings`.
+    ///
+    /// Number properties are checked against their minimum and maximum (and step for integers),
+    /// list properties against their items (unless the list is editable) and path properties
+    /// whether the path exists. Settings that are not set are skipped, as well as properties that
+    /// are not visible or not enabled, since they can't be changed by the user.
+    pub fn validate<D: ObsDataGetters>(
+        &self,
+        settings: &D,
+    ) -> Result<ObsValidationReport, ObsError> {
+        let mut report = ObsValidationReport::default();
+        validate_nodes(self, settings, &mut report)?;
+
+        Ok(report)
+    }
+}
+
+/// Applies the given settings to the properties, so their modified callbacks can show, hide,
+/// enable or disable other properties, and validates the settings against the resulting tree.
+///
+/// The callbacks get a copy of the settings (including their defaults), as they are allowed
+/// to change the settings they are called with.
+pub(crate) fn validate_with_properties<D: ObsDataGetters>(
+    properties: SmartPointerSendable<*mut obs_properties>,
+    settings: &D,
+    runtime: &ObsRuntime,
+) -> Result<ObsValidationReport, ObsError> {
+    let settings_ptr = settings.as_ptr();
+    let runtime_clone = runtime.clone();
+    let tree = run_with_obs!(
+        runtime,
+        (properties, settings_ptr, runtime_clone),
+        move || {
+            let nodes = unsafe {
+                // Safety: All pointers are valid because of smart pointers and we are on the OBS thread.
+                // The copy is created and released within this closure.
+                let copy = libobs::obs_data_get_defaults(settings_ptr.get_ptr());
+                libobs::obs_data_apply(copy, settings_ptr.get_ptr());
+                libobs::obs_properties_apply_settings(properties.get_ptr(), copy);
+                libobs::obs_data_release(copy);
+
+                collect_properties(&runtime_clone, properties.get_ptr())
+            };
+
+            ObsPropertyTree::from(nodes)
+        }
+    )?;
+
+    tree.validate(settings)
+}
+
+fn validate_nodes<D: ObsDataGetters>(
+    tree: &ObsPropertyTree,
+    settings: &D,
+    report: &mut ObsValidationReport,
+) -> Result<(), ObsError> {
+    // The content of hidden or disabled groups is skipped as well
+    for node in tree {
+        if !node.visible() || !node.enabled() {
+            continue;
+        }
+
+        let key = node.name().as_str();
+        let kinds = match node.property() {
+            ObsProperty::Int(p) => match settings.get_int(key)? {
+                Some(value) => check_int(p, value),
+                None => vec![],
+            },
+            ObsProperty::Float(p) => match settings.get_double(key)? {
+                Some(value) => check_float(p, value),
+                None => vec![],
+            },
+            ObsProperty::List(p) => match get_list_value(settings, key, *p.format())? {
+                Some(value) => check_list(p, value),
+                None => vec![],
+            },
+            ObsProperty::Path(p) => match settings.get_string(key)? {
+                Some(value) => check_path(p, &value),
+                None => vec![],
+            },
+            _ => vec![],
+        };
+
+        report.push(key, kinds);
+
+        if let Some(children) = node.children() {
+            validate_nodes(children, settings, report)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn get_list_value<D: ObsDataGetters>(
+    settings: &D,
+    key: &str,
+    format: ObsComboFormat,
+) -> Result<Option<ObsListItemValue>, ObsError> {
+    let value = match format {
+        ObsComboFormat::Invalid => None,
+        ObsComboFormat::Int => settings.get_int(key)?.map(ObsListItemValue::Int),
+        ObsComboFormat::Float => settings.get_double(key)?.map(ObsListItemValue::Float),
+        ObsComboFormat::String => settings.get_string(key)?.map(ObsListItemValue::String),
+        ObsComboFormat::Bool => settings.get_bool(key)?.map(ObsListItemValue::Bool),
+    };
+
+    Ok(value)
+}
+
+fn check_range(value: f64, min: f64, max: f64) -> Vec<ObsValidationIssueKind> {
+    let mut issues = vec![];
+    if value < min {
+        issues.push(ObsValidationIssueKind::BelowMinimum { value, min });
+    }
+
+    if value > max {
+        issues.push(ObsValidationIssueKind::AboveMaximum { value, max });
+    }
+
+    issues
+}
+
+fn check_int(property: &ObsNumberProperty<i32>, value: i64) -> Vec<ObsValidationIssueKind> {
+    let mut issues = check_range(value as f64, *property.min() as f64, *property.max() as f64);
+    issues.extend(check_step(
+        value,
+        *property.min() as i64,
+        *property.step() as i64,
+    ));
+
+    issues
+}
+
+fn check_step(value: i64, min: i64, step: i64) -> Option<ObsValidationIssueKind> {
+    // Values too far from the minimum are already reported as out of range
+    let offset = value.checked_sub(min)?;
+
+    if step > 0 && offset % step != 0 {
+        return Some(ObsValidationIssueKind::NotOnStep {
+            value: value as f64,
+            step: step as f64,
+        });
+    }
+
+    None
+}
+
+// OBS accepts any float within the range, the step only applies to the widget
+fn check_float(property: &ObsNumberProperty<f64>, value: f64) -> Vec<ObsValidationIssueKind> {
+    check_range(value, *property.min(), *property.max())
+}
+
+fn check_list(property: &ObsListProperty, value: ObsListItemValue) -> Vec<ObsValidationIssueKind> {
+    // Editable lists accept values that are not in the list
+    if *property.list_type() == ObsComboType::Editable {
+        return vec![];
+    }
+
+    match property.items().iter().find(|item| *item.value() == value) {
+        Some(item) if *item.disabled() => {
+            vec![ObsValidationIssueKind::DisabledListItem { value }]
+        }
+        Some(_) => vec![],
+        None => vec![ObsValidationIssueKind::NotInList { value }],
+    }
+}
+
+fn check_path(property: &ObsPathProperty, value: &str) -> Vec<ObsValidationIssueKind> {
+    if value.is_empty() {
+        return vec![];
+    }
+
+    let path = Path::new(value);
+    let exists = match property.path_type() {
+        ObsPathType::File => path.is_file(),
+        ObsPathType::Directory => path.is_dir(),
+        // The file itself doesn't have to exist yet, but the directory it is saved to does
+        ObsPathType::FileSave => path
+            .parent()
+            .is_none_or(|p| p.as_os_str().is_empty() || p.is_dir()),
+    };
+
+    if exists {
+        vec![]
+    } else {
+        vec![ObsValidationIssueKind::PathNotFound {
+            path: value.to_string(),
+        }]
+    }
+}
+
+#[test]
+fn test_check_range_within_bounds() {
+    assert!(check_range(50.0, 0.0, 100.0).is_empty());
+    assert!(check_range(0.5, 0.0, 1.0).is_empty());
+}
+
+#[test]
+fn test_check_range_out_of_bounds() {
+    assert_eq!(
+        check_range(-1.0, 0.0, 100.0),
+        vec![ObsValidationIssueKind::BelowMinimum {
+            value: -1.0,
+            min: 0.0
+        }]
+    );
+    assert_eq!(
+        check_range(101.0, 0.0, 100.0),
+        vec![ObsValidationIssueKind::AboveMaximum {
+            value: 101.0,
+            max: 100.0
+        }]
+    );
+}
+
+#[test]
+fn test_check_step() {
+    assert!(check_step(12, 2, 5).is_none());
+    assert!(check_step(13, 2, 0).is_none());
+    assert_eq!(
+        check_step(13, 2, 5),
+        Some(ObsValidationIssueKind::NotOnStep {
+            value: 13.0,
+            step: 5.0
+        })
+    );
+}
+
+#[test]
+fn test_check_step_extreme_values() {
+    assert!(check_step(i64::MAX, -10, 3).is_none());
+    assert!(check_step(i64::MIN, 10, 3).is_none());
+    assert_eq!(
+        check_step(i64::MAX, 0, 2),
+        Some(ObsValidationIssueKind::NotOnStep {
+            value: i64::MAX as f64,
+            step: 2.0
+        })
+    );
+}
@@ -1,7 +1,13 @@
 use std::ffi::CStr;
 
 use crate::{
-    data::ObsDataPointers,
+    data::{
+        properties::{
+            types::{ObsColor, ObsFont, ObsFrameRateValue},
+            ObsFontFlags,
+        },
+        ObsDataPointers,
+    },
     run_with_obs,
     unsafe_send::SmartPointerSendable,
     utils::{ObsError, ObsString},
@@ -19,6 +25,39 @@ unsafe fn has_value(
         || libobs::obs_data_has_default_value(data_ptr.get_ptr(), key.as_ptr().0)
 }
 
+/// Reads a font from the given font sub-object.
+///
+/// # Safety
+/// This function must be called on the OBS runtime and `font` must be a valid pointer.
+#[allow(unknown_lints)]
+#[allow(ensure_obs_call_in_runtime)]
+unsafe fn read_font(font: *mut libobs::obs_data_t) -> ObsFont {
+    let read_string = |key: &str| {
+        let key = ObsString::new(key);
+        let value = libobs::obs_data_get_string(font, key.as_ptr().0);
+        if value.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(value).to_string_lossy().to_string()
+        }
+    };
+
+    let face = read_string("face");
+    let style = read_string("style");
+
+    let size_key = ObsString::new("size");
+    let flags_key = ObsString::new("flags");
+    let size = libobs::obs_data_get_int(font, size_key.as_ptr().0);
+    let flags = libobs::obs_data_get_int(font, flags_key.as_ptr().0);
+
+    ObsFont::new(
+        face,
+        style,
+        size,
+        ObsFontFlags::from_bits_truncate(flags as u32),
+    )
+}
+
 pub trait ObsDataGetters: ObsDataPointers {
     fn get_string<T: Into<ObsString> + Send + Sync>(
         &self,
@@ -150,4 +189,126 @@ pub trait ObsDataGetters: ObsDataPointers {
             Ok(json)
         })?
     }
+
+    /// Gets a color that was stored by a color or color alpha property.
+    fn get_color<T: Into<ObsString> + Sync + Send>(
+        &self,
+        key: T,
+    ) -> Result<Option<ObsColor>, ObsError> {
+        Ok(self.get_int(key)?.map(ObsColor::from_obs_int))
+    }
+
+    /// Gets the default color of a color or color alpha property, ignoring the color the user has set.
+    fn get_default_color<T: Into<ObsString> + Sync + Send>(
+        &self,
+        key: T,
+    ) -> Result<Option<ObsColor>, ObsError> {
+        let key = key.into();
+        let data_ptr = self.as_ptr();
+
+        run_with_obs!(self.runtime(), (data_ptr, key), move || unsafe {
+            // Safety: The pointer is valid because we are using a smart pointer.
+            if !libobs::obs_data_has_default_value(data_ptr.get_ptr(), key.as_ptr().0) {
+                return None;
+            }
+
+            let value = libobs::obs_data_get_default_int(data_ptr.get_ptr(), key.as_ptr().0);
+            Some(ObsColor::from_obs_int(value))
+        })
+    }
+
+    /// Gets a font that was stored by a font property. If no font was set,
+    /// the default font of the object is returned instead.
+    fn get_font<T: Into<ObsString> + Sync + Send>(
+        &self,
+        key: T,
+    ) -> Result<Option<ObsFont>, ObsError> {
+        let key = key.into();
+        let data_ptr = self.as_ptr();
+
+        run_with_obs!(self.runtime(), (data_ptr, key), move || unsafe {
+            // Safety: The pointer is valid because we are using a smart pointer. The returned sub-object is
+            // referenced, so we release it after reading.
+            let mut font = libobs::obs_data_get_obj(data_ptr.get_ptr(), key.as_ptr().0);
+            if font.is_null() {
+                font = libobs::obs_data_get_default_obj(data_ptr.get_ptr(), key.as_ptr().0);
+            }
+
+            if font.is_null() {
+                return None;
+            }
+
+            let result = read_font(font);
+            libobs::obs_data_release(font);
+
+            Some(result)
+        })
+    }
+
+    /// Gets the default font of a font property, ignoring the font the user has set.
+    fn get_default_font<T: Into<ObsString> + Sync + Send>(
+        &self,
+        key: T,
+    ) -> Result<Option<ObsFont>, ObsError> {
+        let key = key.into();
+        let data_ptr = self.as_ptr();
+
+        run_with_obs!(self.runtime(), (data_ptr, key), move || unsafe {
+            // Safety: The pointer is valid because we are using a smart pointer. The returned sub-object is
+            // referenced, so we release it after reading.
+            let font = libobs::obs_data_get_default_obj(data_ptr.get_ptr(), key.as_ptr().0);
+            if font.is_null() {
+                return None;
+            }
+
+            let result = read_font(font);
+            libobs::obs_data_release(font);
+
+            Some(result)
+        })
+    }
+
+    /// Gets the value of a frame rate property.
+    fn get_frame_rate<T: Into<ObsString> + Sync + Send>(
+        &self,
+        key: T,
+    ) -> Result<Option<ObsFrameRateValue>, ObsError> {
+        let key = key.into();
+        let data_ptr = self.as_ptr();
+
+        run_with_obs!(self.runtime(), (data_ptr, key), move || {
+            let mut fps = libobs::media_frames_per_second {
+                numerator: 0,
+                denominator: 0,
+            };
+            let mut option = std::ptr::null();
+
+            let has_value = unsafe {
+                // Safety: The pointer is valid because we are using a smart pointer
+                libobs::obs_data_get_frames_per_second(
+                    data_ptr.get_ptr(),
+                    key.as_ptr().0,
+                    &mut fps,
+                    &mut option,
+                )
+            };
+
+            if !has_value {
+                return None;
+            }
+
+            if !option.is_null() {
+                let option = unsafe {
+                    // Safety: Safe because we did a null check and the string is owned by the data.
+                    CStr::from_ptr(option).to_string_lossy().to_string()
+                };
+
+                if !option.is_empty() {
+                    return Some(ObsFrameRateValue::Option(option));
+                }
+            }
+
+            Some(ObsFrameRateValue::FrameRate(fps.into()))
+        })
+    }
 }
@@ -1,5 +1,8 @@
 use crate::{
-    data::ObsDataPointers,
+    data::{
+        properties::types::{ObsColor, ObsFont, ObsFrameRateValue},
+        ObsDataPointers,
+    },
     run_with_obs,
     utils::{ObsError, ObsString},
 };
@@ -86,4 +89,88 @@ pub trait ObsDataSetters: ObsDataPointers {
 
         Ok(self)
     }
+
+    /// Sets a color in `obs_data` in the format color and color alpha properties use.
+    fn set_color<T: Into<ObsString> + Sync + Send>(
+        &mut self,
+        key: T,
+        value: ObsColor,
+    ) -> Result<&mut Self, ObsError> {
+        self.set_int(key, value.to_obs_int())
+    }
+
+    /// Sets a font in `obs_data` as a sub-object, which is the format font properties use.
+    fn set_font<T: Into<ObsString> + Sync + Send>(
+        &mut self,
+        key: T,
+        value: &ObsFont,
+    ) -> Result<&mut Self, ObsError> {
+        let key = key.into();
+        let face = ObsString::new(value.face());
+        let style = ObsString::new(value.style());
+        let size = *value.size();
+        let flags = value.flags().bits() as i64;
+
+        let data_ptr = self.as_ptr();
+        run_with_obs!(self.runtime(), (key, data_ptr, face, style), move || {
+            let face_key = ObsString::new("face");
+            let style_key = ObsString::new("style");
+            let size_key = ObsString::new("size");
+            let flags_key = ObsString::new("flags");
+
+            unsafe {
+                // Safety: The pointer is valid because we are using a smart pointer and the sub-object is
+                // released after it has been added.
+                let font = libobs::obs_data_create();
+                libobs::obs_data_set_string(font, face_key.as_ptr().0, face.as_ptr().0);
+                libobs::obs_data_set_string(font, style_key.as_ptr().0, style.as_ptr().0);
+                libobs::obs_data_set_int(font, size_key.as_ptr().0, size);
+                libobs::obs_data_set_int(font, flags_key.as_ptr().0, flags);
+
+                libobs::obs_data_set_obj(data_ptr.get_ptr(), key.as_ptr().0, font);
+                libobs::obs_data_release(font);
+            }
+        })?;
+
+        Ok(self)
+    }
+
+    /// Sets the value of a frame rate property in `obs_data`.
+    fn set_frame_rate<T: Into<ObsString> + Sync + Send>(
+        &mut self,
+        key: T,
+        value: ObsFrameRateValue,
+    ) -> Result<&mut Self, ObsError> {
+        let key = key.into();
+        let (fps, option) = match value {
+            ObsFrameRateValue::Option(option) => (
+                libobs::media_frames_per_second {
+                    numerator: 0,
+                    denominator: 0,
+                },
+                Some(ObsString::new(option)),
+            ),
+            ObsFrameRateValue::FrameRate(fps) => (fps.into(), None),
+        };
+
+        let data_ptr = self.as_ptr();
+        run_with_obs!(self.runtime(), (key, data_ptr, option), move || {
+            let option_ptr = option
+                .as_ref()
+                .map(|o| o.as_ptr().0)
+                .unwrap_or(std::ptr::null());
+
+            unsafe {
+                // Safety: The pointer is valid because we are using a smart pointer and the option is owned by this closure.
+                libobs::obs_data_set_frames_per_second(
+                    data_ptr.get_ptr(),
+                    key.as_ptr().0,
+                    fps,
+                    option_ptr,
+                );
+            }
+        })?;
+
+        Ok(self)
+    }
 }
@@ -7,10 +7,10 @@ use crate::{
     data::{
         output::{ObsOutputRef, ObsOutputTrait},
         properties::{
-            ObsProperty, ObsPropertyObject, ObsPropertyObjectPrivate, _ObsPropertiesDropGuard,
-            property_ptr_to_struct,
+            _ObsPropertiesDropGuard, property_ptr_to_struct, property_ptr_to_tree, ObsProperty,
+            ObsPropertyObject, ObsPropertyObjectPrivate, ObsPropertyTree,
         },
-        ObsData,
+        ImmutableObsData, ObsData,
     },
     run_with_obs,
     runtime::ObsRuntime,
@@ -123,6 +123,11 @@ impl ObsPropertyObject for StructName {
         let properties_raw = self.get_properties_raw()?;
         property_ptr_to_struct(properties_raw, self.runtime.clone())
     }
+
+    fn get_properties_tree(&self) -> Result<ObsPropertyTree, ObsError> {
+        let properties_raw = self.get_properties_raw()?;
+        property_ptr_to_tree(properties_raw, self.runtime.clone())
+    }
 }
 
 #[duplicate_item(
@@ -183,4 +188,26 @@ impl ObsPropertyObjectPrivate for StructName {
 
         Ok(SmartPointerSendable::new(ptr.0, drop_guard))
     }
+
+    fn get_defaults_by_id_raw<T: Into<ObsString> + Sync + Send>(
+        id: T,
+        runtime: ObsRuntime,
+    ) -> Result<ImmutableObsData, ObsError> {
+        let id: ObsString = id.into();
+        let data_ptr = run_with_obs!(runtime, (id), move || {
+            let id_ptr = id.as_ptr();
+            let data_ptr = unsafe {
+                // Safety: id_ptr is valid because it comes from ObsString
+                libobs::obs_encoder_defaults(id_ptr.0)
+            };
+
+            if data_ptr.is_null() {
+                Err(ObsError::NullPointer(None))
+            } else {
+                Ok(Sendable(data_ptr))
+            }
+        })??;
+
+        Ok(ImmutableObsData::from_raw_pointer(data_ptr, runtime))
+    }
 }
@@ -288,6 +288,62 @@ pub enum ObsBoundsType {
     MaxOnly = libobs::obs_bounds_type_OBS_BOUNDS_MAX_ONLY,
 }
 
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+/// Describes how a scene item is blended with the items below it.
+pub enum ObsBlendingMode {
+    Normal = libobs::obs_blending_type_OBS_BLEND_NORMAL,
+    Additive = libobs::obs_blending_type_OBS_BLEND_ADDITIVE,
+    Subtract = libobs::obs_blending_type_OBS_BLEND_SUBTRACT,
+    Screen = libobs::obs_blending_type_OBS_BLEND_SCREEN,
+    Multiply = libobs::obs_blending_type_OBS_BLEND_MULTIPLY,
+    Lighten = libobs::obs_blending_type_OBS_BLEND_LIGHTEN,
+    Darken = libobs::obs_blending_type_OBS_BLEND_DARKEN,
+}
+
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+/// Describes in which color space a scene item is blended.
+pub enum ObsBlendingMethod {
+    /// Blends in the color space of the canvas
+    Default = libobs::obs_blending_method_OBS_BLEND_METHOD_DEFAULT,
+    /// Blends without converting to linear sRGB first
+    SrgbOff = libobs::obs_blending_method_OBS_BLEND_METHOD_SRGB_OFF,
+}
+
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+/// Describes how the sources of a scene are duplicated when duplicating the scene.
+pub enum ObsSceneDuplicateType {
+    /// The new scene references the same sources as the original scene
+    Refs = libobs::obs_scene_duplicate_type_OBS_SCENE_DUP_REFS,
+    /// Every source of the original scene is copied, so the new scene can be changed independently
+    Copy = libobs::obs_scene_duplicate_type_OBS_SCENE_DUP_COPY,
+    /// Same as `Refs`, but the new scene is private and not listed with the other sources
+    PrivateRefs = libobs::obs_scene_duplicate_type_OBS_SCENE_DUP_PRIVATE_REFS,
+    /// Same as `Copy`, but the new scene and its copied sources are private
+    PrivateCopy = libobs::obs_scene_duplicate_type_OBS_SCENE_DUP_PRIVATE_COPY,
+}
+
+#[cfg_attr(target_os = "windows", repr(i32))]
+#[cfg_attr(not(target_os = "windows"), repr(u32))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+/// Describes how an item is moved within the order of its parent,
+/// for example a scene item within its scene.
+pub enum ObsOrderMovement {
+    /// Moves the item one position towards the top
+    MoveUp = libobs::obs_order_movement_OBS_ORDER_MOVE_UP,
+    /// Moves the item one position towards the bottom
+    MoveDown = libobs::obs_order_movement_OBS_ORDER_MOVE_DOWN,
+    /// Moves the item to the top, so it is rendered above every other item
+    MoveTop = libobs::obs_order_movement_OBS_ORDER_MOVE_TOP,
+    /// Moves the item to the bottom, so it is rendered below every other item
+    MoveBottom = libobs::obs_order_movement_OBS_ORDER_MOVE_BOTTOM,
+}
+
 bitflags! {
     /// Represents a set of flags.
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -299,3 +355,68 @@ bitflags! {
         const CENTER = libobs::OBS_ALIGN_CENTER;
     }
 }
+
+bitflags! {
+    /// Flags for the delay of an output, see `ObsOutputTrait::set_delay`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct ObsOutputDelayFlags: u32 {
+        /// Keeps the delayed data if the output reconnects, so the stream continues
+        /// where it left off instead of skipping the delayed part.
+        const PRESERVE = libobs::OBS_OUTPUT_DELAY_PRESERVE;
+    }
+}
+
+bitflags! {
+    /// The audio tracks (mixes) of OBS. Sources are mixed into the tracks of their mixer mask,
+    /// see `ObsSourceTrait::set_audio_mixers`, and outputs can record several tracks at once,
+    /// see `ObsOutputTrait::create_and_set_audio_tracks`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct ObsAudioTracks: u32 {
+        const TRACK_1 = 1 << 0;
+        const TRACK_2 = 1 << 1;
+        const TRACK_3 = 1 << 2;
+        const TRACK_4 = 1 << 3;
+        const TRACK_5 = 1 << 4;
+        const TRACK_6 = 1 << 5;
+    }
+}
+
+impl ObsAudioTracks {
+    /// Returns the flag of the given track, starting at 1, or `None` if OBS doesn't have this track.
+    pub fn from_track(track: usize) -> Option<Self> {
+        if track == 0 || track > libobs::MAX_AUDIO_MIXES as usize {
+            return None;
+        }
+
+        Self::from_bits(1 << (track - 1))
+    }
+
+    /// Returns the mixer indices of the contained tracks in ascending order, starting at 0.
+    pub fn mixer_indices(&self) -> Vec<usize> {
+        (0..libobs::MAX_AUDIO_MIXES as usize)
+            .filter(|idx| self.bits() & (1 << idx) != 0)
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+/// Describes how an output is stopped, which only makes a difference if a delay is active.
+pub enum ObsOutputStopMode {
+    /// Sends the remaining delayed data before stopping, which takes as long as the active delay.
+    #[default]
+    Drain,
+    /// Stops immediately and discards the delayed data.
+    Force,
+}
+
+#[test]
+fn test_audio_tracks() {
+    assert_eq!(ObsAudioTracks::from_track(1), Some(ObsAudioTracks::TRACK_1));
+    assert_eq!(ObsAudioTracks::from_track(6), Some(ObsAudioTracks::TRACK_6));
+    assert_eq!(ObsAudioTracks::from_track(0), None);
+    assert_eq!(ObsAudioTracks::from_track(7), None);
+
+    let tracks = ObsAudioTracks::TRACK_1 | ObsAudioTracks::TRACK_3 | ObsAudioTracks::TRACK_6;
+    assert_eq!(tracks.mixer_indices(), vec![0, 2, 5]);
+    assert!(ObsAudioTracks::empty().mixer_indices().is_empty());
+}
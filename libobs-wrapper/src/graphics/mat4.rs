@@ -0,0 +1,144 @@
+use getters0::Getters;
+
+use super::{Vec2, Vec4};
+
+/// Same layout as `struct matrix4` of libobs, which is opaque in the bindings.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct RawMatrix4 {
+    x: libobs::vec4,
+    y: libobs::vec4,
+    z: libobs::vec4,
+    t: libobs::vec4,
+}
+
+/// A 4x4 matrix like the `matrix4` of libobs, with `x`, `y` and `z` being the axes and `t` the translation.
+#[derive(Debug, Clone, Copy, PartialEq, Getters)]
+pub struct Mat4 {
+    #[get_mut]
+    x: Vec4,
+    #[get_mut]
+    y: Vec4,
+    #[get_mut]
+    z: Vec4,
+    #[get_mut]
+    t: Vec4,
+}
+
+impl Default for Mat4 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mat4 {
+    pub fn identity() -> Self {
+        Self {
+            x: Vec4::new(1.0, 0.0, 0.0, 0.0),
+            y: Vec4::new(0.0, 1.0, 0.0, 0.0),
+            z: Vec4::new(0.0, 0.0, 1.0, 0.0),
+            t: Vec4::new(0.0, 0.0, 0.0, 1.0),
+        }
+    }
+
+    /// Creates a matrix by letting `fill` write to a libobs `matrix4`,
+    /// for example with `obs_sceneitem_get_box_transform`.
+    pub(crate) fn read_with<F: FnOnce(*mut libobs::matrix4)>(fill: F) -> Self {
+        let identity = Self::identity();
+        let mut raw = RawMatrix4 {
+            x: identity.x.into(),
+            y: identity.y.into(),
+            z: identity.z.into(),
+            t: identity.t.into(),
+        };
+
+        fill(&mut raw as *mut RawMatrix4 as *mut libobs::matrix4);
+        Self {
+            x: raw.x.into(),
+            y: raw.y.into(),
+            z: raw.z.into(),
+            t: raw.t.into(),
+        }
+    }
+
+    /// Transforms a 2D point the same way libobs does it in `vec3_transform`.
+    pub fn transform_point(&self, point: Vec2) -> Vec2 {
+        Vec2::new(
+            point.x() * self.x.x() + point.y() * self.y.x() + self.t.x(),
+            point.x() * self.x.y() + point.y() * self.y.y() + self.t.y(),
+        )
+    }
+
+    /// Reverts `transform_point`. Returns `None` if the matrix can't be inverted,
+    /// for example because it has a scale of zero.
+    pub fn inverse_transform_point(&self, point: Vec2) -> Option<Vec2> {
+        let det = self.x.x() * self.y.y() - self.y.x() * self.x.y();
+        if det.abs() < f32::EPSILON {
+            return None;
+        }
+
+        let dx = point.x() - self.t.x();
+        let dy = point.y() - self.t.y();
+
+        Some(Vec2::new(
+            (self.y.y() * dx - self.y.x() * dy) / det,
+            (self.x.x() * dy - self.x.y() * dx) / det,
+        ))
+    }
+}
+
+#[test]
+fn test_mat4_raw_layout() {
+    assert_eq!(std::mem::size_of::<RawMatrix4>(), 64);
+    assert_eq!(std::mem::align_of::<RawMatrix4>(), 16);
+
+    let mat = Mat4::read_with(|_| {});
+    assert_eq!(mat, Mat4::identity());
+}
+
+#[test]
+fn test_mat4_transform_point() {
+    // Scaled by 2 and moved to (100, 50)
+    let transform = Mat4::new(
+        Vec4::new(2.0, 0.0, 0.0, 0.0),
+        Vec4::new(0.0, 2.0, 0.0, 0.0),
+        Vec4::new(0.0, 0.0, 1.0, 0.0),
+        Vec4::new(100.0, 50.0, 0.0, 1.0),
+    );
+
+    let scene = transform.transform_point(Vec2::new(10.0, 20.0));
+    assert_eq!(scene.x(), &120.0);
+    assert_eq!(scene.y(), &90.0);
+
+    let local = transform.inverse_transform_point(scene).unwrap();
+    assert_eq!(local.x(), &10.0);
+    assert_eq!(local.y(), &20.0);
+}
+
+#[test]
+fn test_mat4_transform_point_rotated() {
+    // Rotated by 90 degrees
+    let transform = Mat4::new(
+        Vec4::new(0.0, 1.0, 0.0, 0.0),
+        Vec4::new(-1.0, 0.0, 0.0, 0.0),
+        Vec4::new(0.0, 0.0, 1.0, 0.0),
+        Vec4::new(0.0, 0.0, 0.0, 1.0),
+    );
+
+    let scene = transform.transform_point(Vec2::new(1.0, 0.0));
+    assert_eq!(scene.x(), &0.0);
+    assert_eq!(scene.y(), &1.0);
+
+    let local = transform.inverse_transform_point(scene).unwrap();
+    assert_eq!(local.x(), &1.0);
+    assert_eq!(local.y(), &0.0);
+}
+
+#[test]
+fn test_mat4_transform_point_not_invertible() {
+    let zero = Vec4::new(0.0, 0.0, 0.0, 0.0);
+    let transform = Mat4::new(zero, zero, zero, zero);
+    assert!(transform
+        .inverse_transform_point(Vec2::new(1.0, 1.0))
+        .is_none());
+}
@@ -1,4 +1,19 @@
-//! Holds a Vec2 vector, mainly used for positioning the ObsDisplay or ObsSceneItems.
+//! Holds vector, matrix and rectangle types, mainly used for positioning the ObsDisplay or ObsSceneItems.
 
 mod vec2;
 pub use vec2::Vec2;
+
+mod vec3;
+pub use vec3::Vec3;
+
+mod vec4;
+pub use vec4::Vec4;
+
+mod mat4;
+pub use mat4::Mat4;
+
+mod rect;
+pub use rect::Rect;
+
+mod oriented_box;
+pub use oriented_box::OrientedBox;
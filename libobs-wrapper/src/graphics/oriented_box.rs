@@ -0,0 +1,60 @@
+use getters0::Getters;
+
+use super::{Mat4, Rect, Vec2};
+
+/// A possibly rotated rectangle, like the box of a scene item that is drawn when it is selected in OBS Studio.
+#[derive(Debug, Clone, Copy, PartialEq, Getters)]
+#[skip_new]
+pub struct OrientedBox {
+    /// Maps the unit square (from `(0, 0)` to `(1, 1)`) onto the box
+    transform: Mat4,
+}
+
+impl OrientedBox {
+    /// Creates a box from a transform that maps the unit square onto it,
+    /// like the one returned by `obs_sceneitem_get_box_transform`.
+    pub fn from_transform(transform: Mat4) -> Self {
+        Self { transform }
+    }
+
+    /// Returns the corners in the order top left, top right, bottom right, bottom left
+    /// (before rotation).
+    pub fn corners(&self) -> [Vec2; 4] {
+        [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+            .map(|(x, y)| self.transform.transform_point(Vec2::new(x, y)))
+    }
+
+    /// Returns the smallest axis-aligned rectangle that contains this box.
+    pub fn bounding_rect(&self) -> Rect {
+        // There are always four corners
+        Rect::from_points(&self.corners()).unwrap()
+    }
+
+    /// Checks whether the point is within this box, including its edges.
+    /// A box without an area (e.g. scaled to zero) never contains a point.
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.transform
+            .inverse_transform_point(point)
+            .is_some_and(|local| Rect::new(0.0, 0.0, 1.0, 1.0).contains(local))
+    }
+}
+
+#[test]
+fn test_oriented_box_rotated() {
+    // A 100x100 box at (100, 100), rotated by 45 degrees around its top left corner
+    let (sin, cos) = std::f32::consts::FRAC_PI_4.sin_cos();
+    let transform = Mat4::new(
+        super::Vec4::new(100.0 * cos, 100.0 * sin, 0.0, 0.0),
+        super::Vec4::new(-100.0 * sin, 100.0 * cos, 0.0, 0.0),
+        super::Vec4::new(0.0, 0.0, 1.0, 0.0),
+        super::Vec4::new(100.0, 100.0, 0.0, 1.0),
+    );
+    let oriented = OrientedBox::from_transform(transform);
+
+    // Within the axis-aligned rect, but outside of the rotated box
+    assert!(oriented.bounding_rect().contains(Vec2::new(150.0, 105.0)));
+    assert!(!oriented.contains(Vec2::new(150.0, 105.0)));
+
+    // The center of the box
+    assert!(oriented.contains(Vec2::new(100.0, 100.0 + 100.0 * cos)));
+}
@@ -0,0 +1,87 @@
+use getters0::Getters;
+
+use super::Vec2;
+
+/// An axis-aligned rectangle, for example the area covered by a scene item.
+#[derive(Debug, Clone, Copy, PartialEq, Getters)]
+pub struct Rect {
+    #[get_mut]
+    x: f32,
+    #[get_mut]
+    y: f32,
+    #[get_mut]
+    width: f32,
+    #[get_mut]
+    height: f32,
+}
+
+impl Rect {
+    /// Creates the smallest rectangle that contains all given points.
+    pub fn from_points(points: &[Vec2]) -> Option<Self> {
+        let first = points.first()?;
+        let (mut min_x, mut min_y, mut max_x, mut max_y) =
+            (*first.x(), *first.y(), *first.x(), *first.y());
+
+        for point in points.iter().skip(1) {
+            min_x = min_x.min(*point.x());
+            min_y = min_y.min(*point.y());
+            max_x = max_x.max(*point.x());
+            max_y = max_y.max(*point.y());
+        }
+
+        Some(Self::new(min_x, min_y, max_x - min_x, max_y - min_y))
+    }
+
+    /// Checks whether the point is within this rectangle, including its edges.
+    pub fn contains(&self, point: Vec2) -> bool {
+        *point.x() >= self.x
+            && *point.y() >= self.y
+            && *point.x() <= self.x + self.width
+            && *point.y() <= self.y + self.height
+    }
+}
+
+impl From<libobs::gs_rect> for Rect {
+    fn from(raw: libobs::gs_rect) -> Self {
+        Self {
+            x: raw.x as f32,
+            y: raw.y as f32,
+            width: raw.cx as f32,
+            height: raw.cy as f32,
+        }
+    }
+}
+
+/// Rounds the rectangle to whole pixels.
+impl From<Rect> for libobs::gs_rect {
+    fn from(val: Rect) -> Self {
+        libobs::gs_rect {
+            x: val.x.round() as i32,
+            y: val.y.round() as i32,
+            cx: val.width.round() as i32,
+            cy: val.height.round() as i32,
+        }
+    }
+}
+
+#[test]
+fn test_rect_from_points() {
+    let rect = Rect::from_points(&[
+        Vec2::new(10.0, 5.0),
+        Vec2::new(-2.0, 20.0),
+        Vec2::new(4.0, 0.0),
+    ])
+    .unwrap();
+
+    assert_eq!(rect, Rect::new(-2.0, 0.0, 12.0, 20.0));
+    assert!(rect.contains(Vec2::new(0.0, 10.0)));
+    assert!(!rect.contains(Vec2::new(11.0, 10.0)));
+    assert!(Rect::from_points(&[]).is_none());
+}
+
+#[test]
+fn test_rect_gs_rect() {
+    let raw: libobs::gs_rect = Rect::new(1.4, 2.6, 100.0, 50.0).into();
+    assert_eq!((raw.x, raw.y, raw.cx, raw.cy), (1, 3, 100, 50));
+    assert_eq!(Rect::from(raw), Rect::new(1.0, 3.0, 100.0, 50.0));
+}
@@ -0,0 +1,47 @@
+use getters0::Getters;
+
+#[derive(Debug, Clone, Copy, PartialEq, Getters)]
+pub struct Vec3 {
+    #[get_mut]
+    x: f32,
+    #[get_mut]
+    y: f32,
+    #[get_mut]
+    z: f32,
+}
+
+impl From<libobs::vec3> for Vec3 {
+    fn from(raw: libobs::vec3) -> Self {
+        // SAFETY: libobs::vec3 is a C representation with a known layout, the __bindgen_anon_1 must be set
+        let inner = unsafe { raw.__bindgen_anon_1.__bindgen_anon_1 };
+        Self {
+            x: inner.x,
+            y: inner.y,
+            z: inner.z,
+        }
+    }
+}
+
+impl From<Vec3> for libobs::vec3 {
+    fn from(val: Vec3) -> Self {
+        libobs::vec3 {
+            __bindgen_anon_1: libobs::vec3__bindgen_ty_1 {
+                __bindgen_anon_1: libobs::vec3__bindgen_ty_1__bindgen_ty_1 {
+                    x: val.x,
+                    y: val.y,
+                    z: val.z,
+                    w: 0.0,
+                },
+            },
+        }
+    }
+}
+
+#[test]
+fn test_vec3() {
+    let vec_val = Vec3::new(1.0, 2.0, 3.0);
+    let libobs_vec: libobs::vec3 = vec_val.into();
+
+    let original = Vec3::from(libobs_vec);
+    assert_eq!(original, vec_val);
+}
@@ -0,0 +1,50 @@
+use getters0::Getters;
+
+#[derive(Debug, Clone, Copy, PartialEq, Getters)]
+pub struct Vec4 {
+    #[get_mut]
+    x: f32,
+    #[get_mut]
+    y: f32,
+    #[get_mut]
+    z: f32,
+    #[get_mut]
+    w: f32,
+}
+
+impl From<libobs::vec4> for Vec4 {
+    fn from(raw: libobs::vec4) -> Self {
+        // SAFETY: libobs::vec4 is a C representation with a known layout, the __bindgen_anon_1 must be set
+        let inner = unsafe { raw.__bindgen_anon_1.__bindgen_anon_1 };
+        Self {
+            x: inner.x,
+            y: inner.y,
+            z: inner.z,
+            w: inner.w,
+        }
+    }
+}
+
+impl From<Vec4> for libobs::vec4 {
+    fn from(val: Vec4) -> Self {
+        libobs::vec4 {
+            __bindgen_anon_1: libobs::vec4__bindgen_ty_1 {
+                __bindgen_anon_1: libobs::vec4__bindgen_ty_1__bindgen_ty_1 {
+                    x: val.x,
+                    y: val.y,
+                    z: val.z,
+                    w: val.w,
+                },
+            },
+        }
+    }
+}
+
+#[test]
+fn test_vec4() {
+    let vec_val = Vec4::new(1.0, 2.0, 3.0, 4.0);
+    let libobs_vec: libobs::vec4 = vec_val.into();
+
+    let original = Vec4::from(libobs_vec);
+    assert_eq!(original, vec_val);
+}
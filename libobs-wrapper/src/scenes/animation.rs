@@ -0,0 +1,494 @@
+//! Keyframe animations of scene item transforms.
+//!
+//! Animations are driven by the video tick of OBS (`obs_add_tick_callback`), so every rendered
+//! frame gets an updated transform, independent of the thread that started the animation.
+
+use std::{
+    ffi::c_void,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use libobs::{obs_scene_item, obs_transform_info};
+use tokio::sync::watch;
+
+use crate::{
+    graphics::Vec2,
+    impl_obs_drop, run_with_obs,
+    runtime::ObsRuntime,
+    unsafe_send::{Sendable, SmartPointerSendable},
+    utils::ObsError,
+};
+
+use super::{ObsSceneItemCrop, SceneItemTrait};
+
+/// The easing curve that is used to interpolate towards a keyframe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ObsEasing {
+    #[default]
+    Linear,
+    EaseInQuad,
+    EaseOutQuad,
+    EaseInOutQuad,
+    EaseInCubic,
+    EaseOutCubic,
+    EaseInOutCubic,
+}
+
+impl ObsEasing {
+    /// Maps the linear progress `t` (from 0 to 1) onto this curve.
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseInQuad => t * t,
+            Self::EaseOutQuad => 1.0 - (1.0 - t) * (1.0 - t),
+            Self::EaseInOutQuad => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Self::EaseInCubic => t * t * t,
+            Self::EaseOutCubic => 1.0 - (1.0 - t).powi(3),
+            Self::EaseInOutCubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// A keyframe of an `ObsSceneItemAnimation`. Values that are not set keep the value of the previous keyframe
+/// (or the value the scene item had when the animation was started).
+#[derive(Debug, Clone)]
+pub struct ObsTransformKeyframe {
+    time: Duration,
+    easing: ObsEasing,
+    pos: Option<Vec2>,
+    scale: Option<Vec2>,
+    rot: Option<f32>,
+    crop: Option<ObsSceneItemCrop>,
+}
+
+impl ObsTransformKeyframe {
+    /// Creates a keyframe at the given time, measured from the start of the animation.
+    pub fn new(time: Duration) -> Self {
+        Self {
+            time,
+            easing: ObsEasing::Linear,
+            pos: None,
+            scale: None,
+            rot: None,
+            crop: None,
+        }
+    }
+
+    /// The easing curve used to interpolate from the previous keyframe to this one.
+    pub fn set_easing(mut self, easing: ObsEasing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    pub fn set_pos(mut self, pos: Vec2) -> Self {
+        self.pos = Some(pos);
+        self
+    }
+
+    pub fn set_scale(mut self, scale: Vec2) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    pub fn set_rot(mut self, rot: f32) -> Self {
+        self.rot = Some(rot);
+        self
+    }
+
+    pub fn set_crop(mut self, crop: ObsSceneItemCrop) -> Self {
+        self.crop = Some(crop);
+        self
+    }
+}
+
+/// The state of a started animation, see `ObsAnimationHandle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObsAnimationStatus {
+    Running,
+    /// The last keyframe has been reached and applied.
+    Finished,
+    /// The animation was stopped by `ObsAnimationHandle::cancel`.
+    Cancelled,
+}
+
+/// An animation of the position, scale, rotation and crop of a scene item.
+///
+/// ```ignore
+/// let animation = ObsSceneItemAnimation::new()
+///     .add_keyframe(
+///         ObsTransformKeyframe::new(Duration::from_millis(500))
+///             .set_pos(Vec2::new(100.0, 900.0))
+///             .set_easing(ObsEasing::EaseOutCubic),
+///     );
+///
+/// let handle = scene_item.animate(&animation)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ObsSceneItemAnimation {
+    keyframes: Vec<ObsTransformKeyframe>,
+}
+
+/// A keyframe with every value filled in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ResolvedFrame {
+    time: f32,
+    easing: ObsEasing,
+    pos: (f32, f32),
+    scale: (f32, f32),
+    rot: f32,
+    crop: [i32; 4],
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+impl ResolvedFrame {
+    fn interpolate(&self, to: &ResolvedFrame, t: f32) -> ResolvedFrame {
+        let mut crop = [0; 4];
+        for (i, c) in crop.iter_mut().enumerate() {
+            *c = lerp(self.crop[i] as f32, to.crop[i] as f32, t).round() as i32;
+        }
+
+        ResolvedFrame {
+            time: lerp(self.time, to.time, t),
+            easing: to.easing,
+            pos: (lerp(self.pos.0, to.pos.0, t), lerp(self.pos.1, to.pos.1, t)),
+            scale: (
+                lerp(self.scale.0, to.scale.0, t),
+                lerp(self.scale.1, to.scale.1, t),
+            ),
+            rot: lerp(self.rot, to.rot, t),
+            crop,
+        }
+    }
+}
+
+/// Returns the interpolated frame at `elapsed` seconds. `frames` must be sorted by time and not be empty.
+fn sample(frames: &[ResolvedFrame], elapsed: f32) -> ResolvedFrame {
+    let next = frames.iter().position(|f| f.time > elapsed);
+    match next {
+        None => *frames.last().unwrap(),
+        Some(0) => frames[0],
+        Some(i) => {
+            let (from, to) = (&frames[i - 1], &frames[i]);
+            let t = (elapsed - from.time) / (to.time - from.time);
+            from.interpolate(to, to.easing.apply(t))
+        }
+    }
+}
+
+impl ObsSceneItemAnimation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a keyframe to this animation. Keyframes don't need to be added in order.
+    pub fn add_keyframe(mut self, keyframe: ObsTransformKeyframe) -> Self {
+        self.keyframes.push(keyframe);
+        self
+    }
+
+    /// Resolves the keyframes starting from the given values. A keyframe at time zero is
+    /// inserted with the start values if there is none.
+    fn resolve(&self, start: ResolvedFrame) -> Vec<ResolvedFrame> {
+        let mut keyframes = self.keyframes.clone();
+        keyframes.sort_by_key(|k| k.time);
+
+        let mut resolved = Vec::with_capacity(keyframes.len() + 1);
+        let mut current = start;
+        if keyframes.first().is_none_or(|k| !k.time.is_zero()) {
+            resolved.push(current);
+        }
+
+        for keyframe in keyframes {
+            current.time = keyframe.time.as_secs_f32();
+            current.easing = keyframe.easing;
+            if let Some(pos) = keyframe.pos {
+                current.pos = (*pos.x(), *pos.y());
+            }
+            if let Some(scale) = keyframe.scale {
+                current.scale = (*scale.x(), *scale.y());
+            }
+            if let Some(rot) = keyframe.rot {
+                current.rot = rot;
+            }
+            if let Some(crop) = keyframe.crop {
+                current.crop = [*crop.left(), *crop.top(), *crop.right(), *crop.bottom()];
+            }
+
+            resolved.push(current);
+        }
+
+        resolved
+    }
+
+    /// Starts this animation on the given scene item. The current transform of the item is used
+    /// for values that are not set by the first keyframes.
+    ///
+    /// The animation runs until the last keyframe is reached or every clone of the returned handle is dropped.
+    pub fn start<T: SceneItemTrait>(&self, item: &T) -> Result<ObsAnimationHandle, ObsError> {
+        if self.keyframes.is_empty() {
+            return Err(ObsError::InvalidOperation(
+                "An animation needs at least one keyframe".to_string(),
+            ));
+        }
+
+        let info = item.get_transform_info()?;
+        let crop = item.get_crop()?;
+        let pos = info.get_pos();
+        let scale = info.get_scale();
+        let start = ResolvedFrame {
+            time: 0.0,
+            easing: ObsEasing::Linear,
+            pos: (*pos.x(), *pos.y()),
+            scale: (*scale.x(), *scale.y()),
+            rot: info.get_rot(),
+            crop: [*crop.left(), *crop.top(), *crop.right(), *crop.bottom()],
+        };
+
+        let (status, status_rx) = watch::channel(ObsAnimationStatus::Running);
+        let state = Box::new(AnimationState {
+            scene_item: item.as_ptr().clone(),
+            frames: self.resolve(start),
+            animates_pos: self.keyframes.iter().any(|k| k.pos.is_some()),
+            animates_scale: self.keyframes.iter().any(|k| k.scale.is_some()),
+            animates_rot: self.keyframes.iter().any(|k| k.rot.is_some()),
+            animates_crop: self.keyframes.iter().any(|k| k.crop.is_some()),
+            elapsed: Mutex::new(0.0),
+            status,
+        });
+
+        let runtime = item.runtime();
+        let state = Sendable(Box::into_raw(state));
+        run_with_obs!(runtime, (state), move || unsafe {
+            // Safety: The state is freed by the guard only after the callback has been removed
+            libobs::obs_add_tick_callback(Some(animation_tick), state.0 as *mut c_void);
+        })
+        .inspect_err(|_| unsafe {
+            // Safety: The callback was never added, so we are the only owner of the state
+            drop(Box::from_raw(state.0));
+        })?;
+
+        Ok(ObsAnimationHandle {
+            status: status_rx,
+            _guard: Arc::new(_ObsAnimationTickGuard { state, runtime }),
+        })
+    }
+}
+
+/// The data of a running animation. It is accessed by the tick callback on the video thread.
+struct AnimationState {
+    scene_item: SmartPointerSendable<*mut obs_scene_item>,
+    frames: Vec<ResolvedFrame>,
+    animates_pos: bool,
+    animates_scale: bool,
+    animates_rot: bool,
+    animates_crop: bool,
+    /// Seconds since the animation was started
+    elapsed: Mutex<f32>,
+    status: watch::Sender<ObsAnimationStatus>,
+}
+
+impl AnimationState {
+    /// # Safety
+    /// Must be called on the video thread or the OBS thread.
+    unsafe fn apply(&self, frame: &ResolvedFrame) {
+        let item = self.scene_item.get_ptr();
+        if self.animates_pos || self.animates_scale || self.animates_rot {
+            // Safety: this is safe to call because we are filling a struct with zeros
+            let mut info: obs_transform_info = std::mem::zeroed();
+            // Safety: The item is kept alive by the smart pointer
+            libobs::obs_sceneitem_get_info2(item, &mut info);
+
+            if self.animates_pos {
+                info.pos = Vec2::new(frame.pos.0, frame.pos.1).into();
+            }
+            if self.animates_scale {
+                info.scale = Vec2::new(frame.scale.0, frame.scale.1).into();
+            }
+            if self.animates_rot {
+                info.rot = frame.rot;
+            }
+
+            libobs::obs_sceneitem_set_info2(item, &info);
+        }
+
+        if self.animates_crop {
+            let [left, top, right, bottom] = frame.crop;
+            let crop: libobs::obs_sceneitem_crop =
+                ObsSceneItemCrop::new(left, top, right, bottom).into();
+
+            // Safety: The item is kept alive by the smart pointer
+            libobs::obs_sceneitem_set_crop(item, &crop);
+        }
+    }
+}
+
+#[allow(unknown_lints)]
+#[allow(ensure_obs_call_in_runtime)]
+/// # Safety
+/// Always call this function in the video thread of OBS, never call this function directly!
+unsafe extern "C" fn animation_tick(param: *mut c_void, seconds: f32) {
+    // Safety: The state is only freed after this callback has been removed
+    let state = &*(param as *const AnimationState);
+    if *state.status.borrow() != ObsAnimationStatus::Running {
+        return;
+    }
+
+    let elapsed = match state.elapsed.lock() {
+        Ok(mut elapsed) => {
+            *elapsed += seconds;
+            *elapsed
+        }
+        Err(_) => return,
+    };
+
+    state.apply(&sample(&state.frames, elapsed));
+
+    let duration = state.frames.last().map(|f| f.time).unwrap_or_default();
+    if elapsed >= duration {
+        state.status.send_if_modified(|status| {
+            let running = *status == ObsAnimationStatus::Running;
+            if running {
+                *status = ObsAnimationStatus::Finished;
+            }
+
+            running
+        });
+    }
+}
+
+#[derive(Debug)]
+struct _ObsAnimationTickGuard {
+    state: Sendable<*mut AnimationState>,
+    runtime: ObsRuntime,
+}
+
+impl_obs_drop!(_ObsAnimationTickGuard, (state), move || unsafe {
+    // Safety: Removing the callback waits for a running tick, so the state isn't used afterwards
+    libobs::obs_remove_tick_callback(Some(animation_tick), state.0 as *mut c_void);
+    drop(Box::from_raw(state.0));
+});
+
+/// A handle to an animation started with `ObsSceneItemAnimation::start`.
+/// The animation is stopped when every clone of this handle is dropped.
+#[derive(Debug, Clone)]
+pub struct ObsAnimationHandle {
+    status: watch::Receiver<ObsAnimationStatus>,
+    _guard: Arc<_ObsAnimationTickGuard>,
+}
+
+impl ObsAnimationHandle {
+    pub fn status(&self) -> ObsAnimationStatus {
+        *self.status.borrow()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status() == ObsAnimationStatus::Finished
+    }
+
+    /// Returns a receiver that is notified when the animation finishes or is cancelled.
+    ///
+    /// ```ignore
+    /// let mut rx = handle.on_status_changed();
+    /// rx.wait_for(|s| *s != ObsAnimationStatus::Running).await?;
+    /// ```
+    pub fn on_status_changed(&self) -> watch::Receiver<ObsAnimationStatus> {
+        self.status.clone()
+    }
+
+    /// Stops the animation, leaving the scene item at its current transform.
+    pub fn cancel(&self) {
+        // Safety: The state is alive as long as the guard is
+        let state = unsafe { &*self._guard.state.0 };
+        state.status.send_if_modified(|status| {
+            let running = *status == ObsAnimationStatus::Running;
+            if running {
+                *status = ObsAnimationStatus::Cancelled;
+            }
+
+            running
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(time: f32) -> ResolvedFrame {
+        ResolvedFrame {
+            time,
+            easing: ObsEasing::Linear,
+            pos: (0.0, 0.0),
+            scale: (1.0, 1.0),
+            rot: 0.0,
+            crop: [0; 4],
+        }
+    }
+
+    #[test]
+    fn test_easing_endpoints() {
+        for easing in [
+            ObsEasing::Linear,
+            ObsEasing::EaseInQuad,
+            ObsEasing::EaseOutQuad,
+            ObsEasing::EaseInOutQuad,
+            ObsEasing::EaseInCubic,
+            ObsEasing::EaseOutCubic,
+            ObsEasing::EaseInOutCubic,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            assert!((easing.apply(0.5) - 0.5).abs() <= 0.5);
+        }
+
+        assert!(ObsEasing::EaseInCubic.apply(0.25) < 0.25);
+        assert!(ObsEasing::EaseOutCubic.apply(0.25) > 0.25);
+    }
+
+    #[test]
+    fn test_resolve_keeps_unset_values() {
+        let animation = ObsSceneItemAnimation::new()
+            .add_keyframe(ObsTransformKeyframe::new(Duration::from_secs(2)).set_rot(90.0))
+            .add_keyframe(
+                ObsTransformKeyframe::new(Duration::from_secs(1)).set_pos(Vec2::new(10.0, 20.0)),
+            );
+
+        let frames = animation.resolve(frame(0.0));
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0], frame(0.0));
+        assert_eq!(frames[1].pos, (10.0, 20.0));
+        assert_eq!(frames[1].rot, 0.0);
+        assert_eq!(frames[2].pos, (10.0, 20.0));
+        assert_eq!(frames[2].rot, 90.0);
+    }
+
+    #[test]
+    fn test_sample_interpolates() {
+        let mut end = frame(2.0);
+        end.pos = (100.0, 50.0);
+        end.crop = [10, 0, 0, 0];
+        let frames = [frame(0.0), end];
+
+        assert_eq!(sample(&frames, 0.0).pos, (0.0, 0.0));
+        assert_eq!(sample(&frames, 1.0).pos, (50.0, 25.0));
+        assert_eq!(sample(&frames, 1.0).crop, [5, 0, 0, 0]);
+        assert_eq!(sample(&frames, 3.0).pos, (100.0, 50.0));
+    }
+}
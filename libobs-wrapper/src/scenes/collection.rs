@@ -0,0 +1,333 @@
+//! Saves and loads scene collections in the same format as OBS Studio (`basic/scenes/*.json`),
+//! so collections can be edited in OBS Studio and loaded by this crate and vice versa.
+//!
+//! Sources are saved using `obs_save_source`, which includes their settings, filters and,
+//! for scenes, their items with transforms and show/hide transitions.
+
+use std::{
+    ffi::{c_void, CStr},
+    path::Path,
+};
+
+use getters0::Getters;
+use libobs::obs_source_t;
+
+use crate::{
+    context::ObsContext,
+    run_with_obs,
+    sources::ObsSourceRef,
+    unsafe_send::Sendable,
+    utils::{ObsError, ObsString},
+};
+
+use super::ObsSceneRef;
+
+/// The scenes and sources of a scene collection that was loaded by `ObsContext::load_scene_collection`.
+#[derive(Debug, Clone, Getters)]
+#[skip_new]
+pub struct ObsSceneCollection {
+    /// The name of the collection as shown in OBS Studio
+    name: String,
+    /// The scenes of the collection in the order OBS Studio lists them.
+    /// These are stored in the context as well, see `ObsContext::get_scene`.
+    scenes: Vec<ObsSceneRef>,
+    /// Sources that are neither scenes nor groups, for example sources that are not used in any scene.
+    sources: Vec<ObsSourceRef>,
+    /// The scene that was active when the collection was saved. It is set to output channel 0.
+    current_scene: Option<ObsSceneRef>,
+}
+
+unsafe extern "C" fn save_source_filter(is_group: *mut c_void, source: *mut obs_source_t) -> bool {
+    // Safety: is_group is a pointer to a bool that outlives obs_save_sources_filtered
+    let is_group = *(is_group as *const bool);
+
+    !libobs::obs_source_removed(source) && libobs::obs_source_is_group(source) == is_group
+}
+
+unsafe extern "C" fn load_source(private_data: *mut c_void, source: *mut obs_source_t) {
+    // Safety: private_data is the vector passed to obs_load_sources, which releases its own
+    // reference to the source afterwards, so we are taking one to keep the source alive.
+    let sources = &mut *(private_data as *mut Vec<Sendable<*mut obs_source_t>>);
+    let source = libobs::obs_source_get_ref(source);
+    if !source.is_null() {
+        sources.push(Sendable(source));
+    }
+}
+
+/// Copies the string of an OBS function, returning `None` for null pointers.
+///
+/// # Safety
+/// The pointer must be null or point to a valid C string.
+unsafe fn copy_obs_str(ptr: *const std::os::raw::c_char) -> Option<String> {
+    if ptr.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(ptr).to_string_lossy().to_string())
+    }
+}
+
+fn path_to_obs_string(path: &Path) -> Result<ObsString, ObsError> {
+    path.to_str()
+        .map(ObsString::new)
+        .ok_or(ObsError::StringConversionError)
+}
+
+impl ObsContext {
+    /// Saves every source of this context, including scenes, groups and filters, to a scene collection
+    /// at the given path. The file uses the same layout as the scene collections of OBS Studio,
+    /// so it can be imported there.
+    ///
+    /// The scene on output channel 0 is saved as the current scene.
+    pub fn save_scene_collection<T: Into<ObsString>>(
+        &self,
+        name: T,
+        path: &Path,
+    ) -> Result<(), ObsError> {
+        let name: ObsString = name.into();
+        let path = path_to_obs_string(path)?;
+
+        let scene_order: Vec<ObsString> = self
+            .scenes()
+            .read()
+            .map_err(|_| ObsError::LockError("Failed to acquire read lock on scenes".to_string()))?
+            .iter()
+            .map(|s| s.name())
+            .collect();
+
+        run_with_obs!(self.runtime(), (name, path, scene_order), move || {
+            let saved = unsafe {
+                // Safety: Every pointer is created and released within this closure
+                // and all strings are owned by this closure.
+                let data = libobs::obs_data_create();
+
+                let mut is_group = false;
+                let sources = libobs::obs_save_sources_filtered(
+                    Some(save_source_filter),
+                    &mut is_group as *mut bool as *mut c_void,
+                );
+                is_group = true;
+                let groups = libobs::obs_save_sources_filtered(
+                    Some(save_source_filter),
+                    &mut is_group as *mut bool as *mut c_void,
+                );
+
+                let order = libobs::obs_data_array_create();
+                for scene_name in scene_order.iter() {
+                    let item = libobs::obs_data_create();
+                    libobs::obs_data_set_string(item, c"name".as_ptr(), scene_name.as_ptr().0);
+                    libobs::obs_data_array_push_back(order, item);
+                    libobs::obs_data_release(item);
+                }
+
+                let current = libobs::obs_get_output_source(0);
+                if !current.is_null() {
+                    let current_name = libobs::obs_source_get_name(current);
+                    libobs::obs_data_set_string(data, c"current_scene".as_ptr(), current_name);
+                    libobs::obs_data_set_string(
+                        data,
+                        c"current_program_scene".as_ptr(),
+                        current_name,
+                    );
+                    libobs::obs_source_release(current);
+                }
+
+                libobs::obs_data_set_string(data, c"name".as_ptr(), name.as_ptr().0);
+                libobs::obs_data_set_array(data, c"scene_order".as_ptr(), order);
+                libobs::obs_data_set_array(data, c"sources".as_ptr(), sources);
+                libobs::obs_data_set_array(data, c"groups".as_ptr(), groups);
+
+                let saved = libobs::obs_data_save_json_safe(
+                    data,
+                    path.as_ptr().0,
+                    c"tmp".as_ptr(),
+                    c"bak".as_ptr(),
+                );
+
+                libobs::obs_data_array_release(order);
+                libobs::obs_data_array_release(sources);
+                libobs::obs_data_array_release(groups);
+                libobs::obs_data_release(data);
+
+                saved
+            };
+
+            if saved {
+                Ok(())
+            } else {
+                Err(ObsError::IoError(format!(
+                    "Couldn't save scene collection to {}",
+                    path
+                )))
+            }
+        })?
+    }
+
+    /// Loads a scene collection that was saved by `save_scene_collection` or by OBS Studio.
+    /// This should be done on a fresh context, as sources with the same names are not replaced.
+    ///
+    /// Every loaded scene is stored in this context and its items are stored in the scene,
+    /// just like items added with `add_source`. The scene that was active when the collection was
+    /// saved is set to output channel 0.
+    pub fn load_scene_collection(&mut self, path: &Path) -> Result<ObsSceneCollection, ObsError> {
+        let path = path_to_obs_string(path)?;
+        let runtime = self.runtime().clone();
+
+        let (name, current_scene_name, scene_order, loaded) =
+            run_with_obs!(runtime, (path), move || {
+                unsafe {
+                    // Safety: Every pointer is checked for null, created and released within this closure.
+                    let data = libobs::obs_data_create_from_json_file_safe(
+                        path.as_ptr().0,
+                        c"bak".as_ptr(),
+                    );
+                    if data.is_null() {
+                        return Err(ObsError::IoError(format!(
+                            "Couldn't read scene collection from {}",
+                            path
+                        )));
+                    }
+
+                    let name = copy_obs_str(libobs::obs_data_get_string(data, c"name".as_ptr()))
+                        .unwrap_or_default();
+
+                    let mut current_scene_name = copy_obs_str(libobs::obs_data_get_string(
+                        data,
+                        c"current_program_scene".as_ptr(),
+                    ))
+                    .filter(|n| !n.is_empty());
+                    if current_scene_name.is_none() {
+                        current_scene_name = copy_obs_str(libobs::obs_data_get_string(
+                            data,
+                            c"current_scene".as_ptr(),
+                        ))
+                        .filter(|n| !n.is_empty());
+                    }
+
+                    let mut scene_order = Vec::new();
+                    let order = libobs::obs_data_get_array(data, c"scene_order".as_ptr());
+                    if !order.is_null() {
+                        for i in 0..libobs::obs_data_array_count(order) {
+                            let item = libobs::obs_data_array_item(order, i);
+                            if let Some(n) =
+                                copy_obs_str(libobs::obs_data_get_string(item, c"name".as_ptr()))
+                            {
+                                scene_order.push(n);
+                            }
+                            libobs::obs_data_release(item);
+                        }
+                        libobs::obs_data_array_release(order);
+                    }
+
+                    let mut loaded: Vec<Sendable<*mut obs_source_t>> = Vec::new();
+                    let sources = libobs::obs_data_get_array(data, c"sources".as_ptr());
+                    let groups = libobs::obs_data_get_array(data, c"groups".as_ptr());
+                    if !sources.is_null() {
+                        // Groups are loaded together with the other sources, just like OBS Studio does it
+                        if !groups.is_null() {
+                            libobs::obs_data_array_push_back_array(sources, groups);
+                        }
+
+                        libobs::obs_load_sources(
+                            sources,
+                            Some(load_source),
+                            &mut loaded as *mut _ as *mut c_void,
+                        );
+                        libobs::obs_data_array_release(sources);
+                    }
+
+                    if !groups.is_null() {
+                        libobs::obs_data_array_release(groups);
+                    }
+
+                    libobs::obs_data_release(data);
+
+                    // Sorting the sources into scenes, groups and all others
+                    let loaded: Vec<_> = loaded
+                        .into_iter()
+                        .map(|source| {
+                            let source_name = copy_obs_str(libobs::obs_source_get_name(source.0))
+                                .unwrap_or_default();
+                            let scene = libobs::obs_scene_from_source(source.0);
+                            let is_group = libobs::obs_source_is_group(source.0);
+
+                            (source, source_name, Sendable(scene), is_group)
+                        })
+                        .collect();
+
+                    Ok((name, current_scene_name, scene_order, loaded))
+                }
+            })??;
+
+        let mut scenes = Vec::new();
+        let mut sources = Vec::new();
+        let mut to_release = Vec::new();
+        let mut result = Ok(());
+        for (source, source_name, scene, is_group) in loaded {
+            if result.is_err() {
+                to_release.push(source);
+                continue;
+            }
+
+            if !scene.0.is_null() {
+                // The scene takes over our reference
+                match ObsSceneRef::new_from_raw(ObsString::new(source_name), scene, runtime.clone())
+                {
+                    Ok(scene) => scenes.push(scene),
+                    Err(e) => result = Err(e),
+                }
+                continue;
+            }
+
+            // Groups are kept alive by the items of their scenes
+            if !is_group {
+                match ObsSourceRef::new_from_raw(source.clone(), runtime.clone()) {
+                    Ok(source) => sources.push(source),
+                    Err(e) => result = Err(e),
+                }
+            }
+
+            to_release.push(source);
+        }
+
+        run_with_obs!(runtime, (to_release), move || {
+            for source in to_release {
+                unsafe {
+                    // Safety: We took a reference to every loaded source in `load_source`
+                    libobs::obs_source_release(source.0);
+                }
+            }
+        })?;
+        result?;
+
+        for scene in scenes.iter() {
+            scene.attach_existing_items()?;
+        }
+
+        // Ordering the scenes the same way OBS Studio does
+        scenes.sort_by_key(|s| {
+            let name = s.name().to_string();
+            scene_order
+                .iter()
+                .position(|n| *n == name)
+                .unwrap_or(usize::MAX)
+        });
+
+        self.scenes()
+            .write()
+            .map_err(|_| ObsError::LockError("Failed to acquire write lock on scenes".to_string()))?
+            .extend(scenes.iter().cloned());
+
+        let current_scene = current_scene_name
+            .and_then(|n| scenes.iter().find(|s| s.name().to_string() == n).cloned());
+        if let Some(scene) = &current_scene {
+            scene.set_to_channel(0)?;
+        }
+
+        Ok(ObsSceneCollection {
+            name,
+            scenes,
+            sources,
+            current_scene,
+        })
+    }
+}
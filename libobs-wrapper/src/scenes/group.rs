@@ -0,0 +1,283 @@
+//! Groups hold multiple scene items, so they can be moved, scaled and rotated together.
+//! A group is a scene item itself, which is why `ObsSceneGroupRef` implements `SceneItemTrait`.
+//! The transform of the group (see `SceneItemTrait::get_transform_info`) is applied on top of the
+//! transforms of its items, which are relative to the group.
+
+use std::{ffi::c_void, sync::Arc};
+
+use libobs::{obs_scene_item, obs_scene_t};
+
+use crate::{
+    graphics::{Mat4, Vec2},
+    run_with_obs,
+    runtime::ObsRuntime,
+    sources::{ObsSourceRef, ObsSourceTrait},
+    unsafe_send::{Sendable, SmartPointerSendable},
+    utils::{ObsError, ObsString},
+};
+
+use super::{
+    enum_scene_item, wrap_enumerated_items, EnumItemsData, ObsSceneItemRef, ObsSceneRef,
+    SceneItemTrait,
+};
+
+#[derive(Debug, Clone)]
+/// A group within a scene, created by `ObsSceneRef::add_group` or found by `ObsSceneRef::get_group`.
+pub struct ObsSceneGroupRef {
+    item: ObsSceneItemRef<ObsSourceRef>,
+    scene_ptr: SmartPointerSendable<*mut obs_scene_t>,
+    runtime: ObsRuntime,
+}
+
+impl ObsSceneGroupRef {
+    fn new_from_raw(
+        scene_ptr: SmartPointerSendable<*mut obs_scene_t>,
+        group: Sendable<*mut obs_scene_item>,
+        remove_on_drop: bool,
+        runtime: ObsRuntime,
+    ) -> Result<Self, ObsError> {
+        let item = ObsSceneItemRef::new_from_raw(
+            scene_ptr.clone(),
+            group,
+            remove_on_drop,
+            runtime.clone(),
+        )?;
+
+        Ok(Self {
+            item,
+            scene_ptr,
+            runtime,
+        })
+    }
+
+    /// Returns the source of this group, which holds the scene of the group.
+    pub fn inner_source(&self) -> &ObsSourceRef {
+        self.item.inner_source()
+    }
+
+    /// Moves the given scene item into this group. The item must be in the same scene as this group.
+    /// The position of the item in the scene stays the same, as its transform is converted to be relative to the group.
+    pub fn add_item<K: SceneItemTrait + ?Sized>(&self, item: &K) -> Result<(), ObsError> {
+        let group_ptr = self.item.as_ptr().clone();
+        let item_ptr = item.as_ptr().clone();
+
+        run_with_obs!(self.runtime, (group_ptr, item_ptr), move || {
+            unsafe {
+                // Safety: Both pointers are valid as they are safe pointers
+                let parent = libobs::obs_sceneitem_get_scene(group_ptr.get_ptr());
+                if libobs::obs_sceneitem_get_scene(item_ptr.get_ptr()) != parent {
+                    return Err(ObsError::InvalidOperation(
+                        "The scene item must be in the same scene as the group".to_string(),
+                    ));
+                }
+
+                libobs::obs_sceneitem_group_add_item(group_ptr.get_ptr(), item_ptr.get_ptr());
+            }
+
+            Ok(())
+        })?
+    }
+
+    /// Moves the given scene item out of this group and back into the scene of the group.
+    /// The position of the item in the scene stays the same.
+    pub fn remove_item<K: SceneItemTrait + ?Sized>(&self, item: &K) -> Result<(), ObsError> {
+        let group_ptr = self.item.as_ptr().clone();
+        let item_ptr = item.as_ptr().clone();
+
+        run_with_obs!(self.runtime, (group_ptr, item_ptr), move || {
+            unsafe {
+                // Safety: Both pointers are valid as they are safe pointers
+                let group_scene = libobs::obs_sceneitem_group_get_scene(group_ptr.get_ptr());
+                if libobs::obs_sceneitem_get_scene(item_ptr.get_ptr()) != group_scene {
+                    return Err(ObsError::InvalidOperation(
+                        "The scene item is not in this group".to_string(),
+                    ));
+                }
+
+                libobs::obs_sceneitem_group_remove_item(group_ptr.get_ptr(), item_ptr.get_ptr());
+            }
+
+            Ok(())
+        })?
+    }
+
+    /// Gets every item of this group, ordered from bottom to top.
+    /// Dropping the returned items keeps them in the group.
+    pub fn enum_items(&self) -> Result<Vec<ObsSceneItemRef<ObsSourceRef>>, ObsError> {
+        let group_ptr = self.item.as_ptr().clone();
+        let item_ptrs = run_with_obs!(self.runtime, (group_ptr), move || {
+            let mut data = EnumItemsData {
+                items: Vec::new(),
+                add_ref: true,
+            };
+
+            unsafe {
+                // Safety: The group pointer is valid because of the smart pointer and the data
+                // outlives the call, as the items are enumerated synchronously.
+                libobs::obs_sceneitem_group_enum_items(
+                    group_ptr.get_ptr(),
+                    Some(enum_scene_item),
+                    &mut data as *mut _ as *mut c_void,
+                );
+            }
+
+            data.items
+        })?;
+
+        wrap_enumerated_items(
+            self.scene_ptr.clone(),
+            item_ptrs,
+            false,
+            self.runtime.clone(),
+        )
+    }
+
+    /// Converts a position relative to this group (as used by the transforms of its items)
+    /// to a position in the scene of this group.
+    pub fn local_to_scene(&self, position: Vec2) -> Result<Vec2, ObsError> {
+        Ok(self.get_draw_transform()?.transform_point(position))
+    }
+
+    /// Converts a position in the scene of this group to a position relative to this group,
+    /// which can be used for the transforms of its items.
+    pub fn scene_to_local(&self, position: Vec2) -> Result<Vec2, ObsError> {
+        self.get_draw_transform()?
+            .inverse_transform_point(position)
+            .ok_or_else(|| {
+                ObsError::InvalidOperation(
+                    "The transform of the group can't be inverted".to_string(),
+                )
+            })
+    }
+
+    fn get_draw_transform(&self) -> Result<Mat4, ObsError> {
+        let group_ptr = self.item.as_ptr().clone();
+        run_with_obs!(self.runtime, (group_ptr), move || {
+            Mat4::read_with(|transform| unsafe {
+                // Safety: The pointer is valid as it is a safe pointer. The transform is only updated
+                // on the next video tick by default, so we are forcing the update here.
+                libobs::obs_sceneitem_force_update_transform(group_ptr.get_ptr());
+                libobs::obs_sceneitem_get_draw_transform(group_ptr.get_ptr(), transform);
+            })
+        })
+    }
+}
+
+impl SceneItemTrait for ObsSceneGroupRef {
+    fn as_ptr(&self) -> &SmartPointerSendable<*mut obs_scene_item> {
+        self.item.as_ptr()
+    }
+
+    fn runtime(&self) -> ObsRuntime {
+        self.runtime.clone()
+    }
+
+    fn inner_source_dyn(&self) -> &dyn ObsSourceTrait {
+        self.item.inner_source_dyn()
+    }
+
+    fn inner_source_dyn_mut(&mut self) -> &mut dyn ObsSourceTrait {
+        self.item.inner_source_dyn_mut()
+    }
+}
+
+impl ObsSceneRef {
+    /// Creates a new, empty group in this scene. Use `ObsSceneGroupRef::add_item` to move items into it.
+    ///
+    /// Just like sources added by `add_source`, the group is stored in this scene and
+    /// can be removed with `remove_scene_item`, which also removes every item in the group.
+    pub fn add_group<T: Into<ObsString>>(&mut self, name: T) -> Result<ObsSceneGroupRef, ObsError> {
+        let name: ObsString = name.into();
+        let scene_ptr = self.scene.clone();
+
+        let group_ptr = run_with_obs!(self.runtime, (scene_ptr, name), move || {
+            let group_ptr = unsafe {
+                // Safety: The scene pointer is valid because of the smart pointer and the name is owned by this closure
+                libobs::obs_scene_add_group2(scene_ptr.get_ptr(), name.as_ptr().0, true)
+            };
+
+            if group_ptr.is_null() {
+                return Err(ObsError::NullPointer(None));
+            }
+
+            unsafe {
+                // Safety: The group was just created, so it is valid. The reference is released by the drop guard.
+                libobs::obs_sceneitem_addref(group_ptr);
+            }
+
+            Ok(Sendable(group_ptr))
+        })??;
+
+        let group = ObsSceneGroupRef::new_from_raw(
+            self.scene.clone(),
+            group_ptr,
+            true,
+            self.runtime.clone(),
+        )?;
+
+        self.attached_scene_items
+            .write()
+            .map_err(|e| ObsError::LockError(format!("{:?}", e)))?
+            .entry(Arc::new(Box::new(group.inner_source().clone())))
+            .or_insert_with(Vec::new)
+            .push(Arc::new(Box::new(group.clone())));
+
+        Ok(group)
+    }
+
+    /// Gets the group with the given name in this scene, for example a group of a scene
+    /// that was loaded from a scene collection. Returns `None` if there is no such group.
+    ///
+    /// Dropping the returned group keeps it in the scene.
+    pub fn get_group<T: Into<ObsString>>(
+        &self,
+        name: T,
+    ) -> Result<Option<ObsSceneGroupRef>, ObsError> {
+        let name: ObsString = name.into();
+        let scene_ptr = self.scene.clone();
+
+        let group_ptr = run_with_obs!(self.runtime, (scene_ptr, name), move || {
+            let group_ptr = unsafe {
+                // Safety: The scene pointer is valid because of the smart pointer and the name is owned by this closure
+                libobs::obs_scene_get_group(scene_ptr.get_ptr(), name.as_ptr().0)
+            };
+
+            if group_ptr.is_null() {
+                return None;
+            }
+
+            unsafe {
+                // Safety: The group is valid while we are on the OBS thread. The reference is released by the drop guard.
+                libobs::obs_sceneitem_addref(group_ptr);
+            }
+
+            Some(Sendable(group_ptr))
+        })?;
+
+        group_ptr
+            .map(|ptr| {
+                ObsSceneGroupRef::new_from_raw(self.scene.clone(), ptr, false, self.runtime.clone())
+            })
+            .transpose()
+    }
+
+    /// Removes the given group from this scene and moves its items back into the scene,
+    /// keeping their position in the scene.
+    pub fn ungroup(&mut self, group: ObsSceneGroupRef) -> Result<(), ObsError> {
+        let group_ptr = group.as_ptr().clone();
+        run_with_obs!(self.runtime, (group_ptr), move || unsafe {
+            // Safety: The pointer is valid as it is a safe pointer
+            libobs::obs_sceneitem_group_ungroup2(group_ptr.get_ptr(), true);
+        })?;
+
+        self.attached_scene_items
+            .write()
+            .map_err(|e| ObsError::LockError(format!("{:?}", e)))?
+            .retain(|_, items| {
+                items.retain(|item| item.as_ptr().get_ptr() != group_ptr.get_ptr());
+                !items.is_empty()
+            });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,492 @@
+//! Computes and applies layouts for multiple scene items, like a grid of camera feeds
+//! or a picture-in-picture composition.
+//!
+//! Every item is placed into a cell using the bounds of its transform, so the layout
+//! does not depend on the size of the sources.
+
+use std::ffi::c_void;
+
+use libobs::{obs_scene_item, obs_scene_t, obs_transform_info, obs_video_info};
+
+use crate::{
+    enums::{ObsAlignment, ObsBoundsType},
+    graphics::Vec2,
+    run_with_obs,
+    unsafe_send::Sendable,
+    utils::ObsError,
+};
+
+use super::{ObsSceneRef, ObsTransformInfo, ObsTransformInfoBuilder, SceneItemTrait};
+
+/// A corner of the canvas, used for picture-in-picture layouts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObsLayoutCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// An edge of the canvas, used for the strip of a focus layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObsLayoutEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObsSplitDirection {
+    /// The items are placed next to each other.
+    Horizontal,
+    /// The items are stacked on top of each other.
+    Vertical,
+}
+
+/// How the items are arranged on the canvas. The first item is the one at the top left
+/// or, for picture-in-picture and focus layouts, the main item.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ObsLayoutKind {
+    /// A grid with the given number of columns. If `None`, the grid is as square as possible.
+    /// The last row is centered if it isn't full.
+    Grid { columns: Option<u32> },
+    /// The first item fills the canvas, all others are stacked in the given corner.
+    PictureInPicture {
+        corner: ObsLayoutCorner,
+        /// The width of the small items relative to the canvas width (e.g. `0.25`)
+        size: f32,
+        /// Distance in pixels between the small items and the edges of the canvas
+        margin: f32,
+    },
+    /// The canvas is split into equally sized parts.
+    Split { direction: ObsSplitDirection },
+    /// The first item takes most of the canvas, all others are placed in a strip along the given edge.
+    FocusStrip {
+        edge: ObsLayoutEdge,
+        /// The size of the strip relative to the canvas (e.g. `0.2`)
+        size: f32,
+    },
+}
+
+/// How an item is fitted into its cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ObsLayoutAspect {
+    /// Keeps the aspect ratio, the item might not fill the whole cell.
+    #[default]
+    Fit,
+    /// Keeps the aspect ratio and crops the item to fill the whole cell.
+    Fill,
+    /// Stretches the item to the size of the cell.
+    Stretch,
+}
+
+/// A rectangle on the canvas that one item is placed into.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ObsLayoutCell {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl ObsLayoutCell {
+    fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width: width.max(0.0),
+            height: height.max(0.0),
+        }
+    }
+
+    /// Splits this cell into `count` equal parts with `padding` between them.
+    fn split(&self, count: usize, direction: ObsSplitDirection, padding: f32) -> Vec<Self> {
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let gaps = padding * (count - 1) as f32;
+        (0..count)
+            .map(|i| match direction {
+                ObsSplitDirection::Horizontal => {
+                    let width = (self.width - gaps) / count as f32;
+                    Self::new(
+                        self.x + i as f32 * (width + padding),
+                        self.y,
+                        width,
+                        self.height,
+                    )
+                }
+                ObsSplitDirection::Vertical => {
+                    let height = (self.height - gaps) / count as f32;
+                    Self::new(
+                        self.x,
+                        self.y + i as f32 * (height + padding),
+                        self.width,
+                        height,
+                    )
+                }
+            })
+            .collect()
+    }
+}
+
+/// Describes a layout that can be applied to a scene using `ObsSceneRef::apply_layout`.
+///
+/// ```ignore
+/// let layout = ObsLayout::new(ObsLayoutKind::Grid { columns: None })
+///     .set_padding(8.0)
+///     .set_aspect(ObsLayoutAspect::Fill);
+///
+/// scene.apply_layout(&layout, &[&camera_1, &camera_2, &camera_3])?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ObsLayout {
+    kind: ObsLayoutKind,
+    aspect: ObsLayoutAspect,
+    padding: f32,
+}
+
+impl ObsLayout {
+    pub fn new(kind: ObsLayoutKind) -> Self {
+        Self {
+            kind,
+            aspect: ObsLayoutAspect::Fit,
+            padding: 0.0,
+        }
+    }
+
+    pub fn set_aspect(mut self, aspect: ObsLayoutAspect) -> Self {
+        self.aspect = aspect;
+        self
+    }
+
+    /// Sets the space in pixels between the items and around the edges of the canvas.
+    pub fn set_padding(mut self, padding: f32) -> Self {
+        self.padding = padding.max(0.0);
+        self
+    }
+
+    /// Computes the cell of each of the `count` items on a canvas of the given size.
+    /// Fails if the canvas has no width or height.
+    pub fn compute_cells(
+        &self,
+        base_width: u32,
+        base_height: u32,
+        count: usize,
+    ) -> Result<Vec<ObsLayoutCell>, ObsError> {
+        if base_width == 0 || base_height == 0 {
+            return Err(ObsError::InvalidOperation(format!(
+                "Can't compute a layout for a canvas of {}x{}",
+                base_width, base_height
+            )));
+        }
+
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let (width, height) = (base_width as f32, base_height as f32);
+        let p = self.padding;
+        let area = ObsLayoutCell::new(p, p, width - 2.0 * p, height - 2.0 * p);
+
+        let cells = match self.kind {
+            ObsLayoutKind::Grid { columns } => {
+                let columns = columns
+                    .map(|c| c.max(1) as usize)
+                    .unwrap_or_else(|| (count as f32).sqrt().ceil() as usize)
+                    .min(count);
+                let rows = count.div_ceil(columns);
+
+                let mut cells = Vec::with_capacity(count);
+                for (row, row_cell) in area
+                    .split(rows, ObsSplitDirection::Vertical, p)
+                    .into_iter()
+                    .enumerate()
+                {
+                    let mut row_cells = row_cell.split(columns, ObsSplitDirection::Horizontal, p);
+                    let in_row = (count - row * columns).min(columns);
+
+                    // Centering the last row
+                    let offset = (columns - in_row) as f32 * (row_cells[0].width + p) / 2.0;
+                    row_cells.truncate(in_row);
+                    cells.extend(row_cells.into_iter().map(|mut c| {
+                        c.x += offset;
+                        c
+                    }));
+                }
+
+                cells
+            }
+            ObsLayoutKind::PictureInPicture {
+                corner,
+                size,
+                margin,
+            } => {
+                let mut cells = vec![area];
+                let small_width = width * size.clamp(0.0, 1.0);
+                let small_height = small_width * height / width;
+
+                let left = matches!(
+                    corner,
+                    ObsLayoutCorner::TopLeft | ObsLayoutCorner::BottomLeft
+                );
+                let top = matches!(corner, ObsLayoutCorner::TopLeft | ObsLayoutCorner::TopRight);
+                let x = if left {
+                    margin
+                } else {
+                    width - margin - small_width
+                };
+
+                for i in 0..count - 1 {
+                    let offset = margin + i as f32 * (small_height + p);
+                    let y = if top {
+                        offset
+                    } else {
+                        height - offset - small_height
+                    };
+
+                    cells.push(ObsLayoutCell::new(x, y, small_width, small_height));
+                }
+
+                cells
+            }
+            ObsLayoutKind::Split { direction } => area.split(count, direction, p),
+            ObsLayoutKind::FocusStrip { edge, size } => {
+                if count == 1 {
+                    return Ok(vec![area]);
+                }
+
+                let size = size.clamp(0.0, 1.0);
+                let (main, strip, direction) = match edge {
+                    ObsLayoutEdge::Top | ObsLayoutEdge::Bottom => {
+                        let strip_height = area.height * size;
+                        let main_height = area.height - strip_height - p;
+                        let (main_y, strip_y) = if edge == ObsLayoutEdge::Top {
+                            (area.y + strip_height + p, area.y)
+                        } else {
+                            (area.y, area.y + main_height + p)
+                        };
+
+                        (
+                            ObsLayoutCell::new(area.x, main_y, area.width, main_height),
+                            ObsLayoutCell::new(area.x, strip_y, area.width, strip_height),
+                            ObsSplitDirection::Horizontal,
+                        )
+                    }
+                    ObsLayoutEdge::Left | ObsLayoutEdge::Right => {
+                        let strip_width = area.width * size;
+                        let main_width = area.width - strip_width - p;
+                        let (main_x, strip_x) = if edge == ObsLayoutEdge::Left {
+                            (area.x + strip_width + p, area.x)
+                        } else {
+                            (area.x, area.x + main_width + p)
+                        };
+
+                        (
+                            ObsLayoutCell::new(main_x, area.y, main_width, area.height),
+                            ObsLayoutCell::new(strip_x, area.y, strip_width, area.height),
+                            ObsSplitDirection::Vertical,
+                        )
+                    }
+                };
+
+                let mut cells = vec![main];
+                cells.extend(strip.split(count - 1, direction, p));
+                cells
+            }
+        };
+
+        Ok(cells)
+    }
+
+    /// Computes the transform of each of the `count` items on a canvas of the given size.
+    /// The canvas size is usually the base size of the `ObsVideoInfo`.
+    /// Fails if the canvas has no width or height.
+    pub fn compute(
+        &self,
+        base_width: u32,
+        base_height: u32,
+        count: usize,
+    ) -> Result<Vec<ObsTransformInfo>, ObsError> {
+        let (bounds_type, crop_to_bounds) = match self.aspect {
+            ObsLayoutAspect::Fit => (ObsBoundsType::ScaleInner, false),
+            ObsLayoutAspect::Fill => (ObsBoundsType::ScaleOuter, true),
+            ObsLayoutAspect::Stretch => (ObsBoundsType::Stretch, false),
+        };
+
+        let infos = self
+            .compute_cells(base_width, base_height, count)?
+            .into_iter()
+            .map(|cell| {
+                ObsTransformInfoBuilder::new()
+                    .set_pos(Vec2::new(
+                        cell.x + cell.width / 2.0,
+                        cell.y + cell.height / 2.0,
+                    ))
+                    .set_alignment(ObsAlignment::CENTER)
+                    .set_bounds(Vec2::new(cell.width, cell.height))
+                    .set_bounds_type(bounds_type)
+                    .set_bounds_alignment(ObsAlignment::CENTER)
+                    .set_crop_to_bounds(crop_to_bounds)
+                    .build(base_width, base_height)
+            })
+            .collect();
+
+        Ok(infos)
+    }
+}
+
+/// The data that is passed to `apply_layout_atomic`.
+struct LayoutUpdate {
+    items: Vec<(Sendable<*mut obs_scene_item>, Sendable<obs_transform_info>)>,
+}
+
+unsafe extern "C" fn apply_layout_atomic(param: *mut c_void, _scene: *mut obs_scene_t) {
+    // Safety: param is always a pointer to LayoutUpdate that outlives the atomic update
+    let update = &*(param as *const LayoutUpdate);
+    for (item, info) in update.items.iter() {
+        // Safety: The items are kept alive by the caller of obs_scene_atomic_update
+        libobs::obs_sceneitem_set_info2(item.0, &info.0);
+    }
+}
+
+impl ObsSceneRef {
+    /// Arranges the given items of this scene using the layout and the base size of the current video info.
+    /// The items are placed in the given order, so the first item is the main item of
+    /// picture-in-picture and focus layouts.
+    ///
+    /// All transforms are applied at once, so no frame is rendered with a partially applied layout.
+    /// Locked items keep their cell, but are not moved.
+    pub fn apply_layout(
+        &self,
+        layout: &ObsLayout,
+        items: &[&dyn SceneItemTrait],
+    ) -> Result<(), ObsError> {
+        let scene_ptr = self.as_ptr();
+        let item_ptrs = items.iter().map(|i| i.as_ptr().clone()).collect::<Vec<_>>();
+
+        let (base_width, base_height) = run_with_obs!(self.runtime, move || {
+            let mut ovi = std::mem::MaybeUninit::<obs_video_info>::uninit();
+            let success = unsafe {
+                // Safety: This is safe because we are providing a valid pointer to be filled
+                libobs::obs_get_video_info(ovi.as_mut_ptr())
+            };
+
+            if success {
+                let ovi = unsafe {
+                    // Safety: This is safe because libobs filled the pointer and returned success
+                    ovi.assume_init()
+                };
+
+                Ok((ovi.base_width, ovi.base_height))
+            } else {
+                Err(ObsError::NullPointer(Some(
+                    "Failed to get video info".to_string(),
+                )))
+            }
+        })??;
+
+        let infos = layout.compute(base_width, base_height, items.len())?;
+        let update = item_ptrs
+            .iter()
+            .zip(infos)
+            .map(|(item, info)| (Sendable(item.get_ptr()), Sendable(info.0)))
+            .collect::<Vec<_>>();
+
+        run_with_obs!(self.runtime, (scene_ptr, item_ptrs), move || unsafe {
+            // Safety: The scene and the items are valid because of the smart pointers
+            for item in item_ptrs.iter() {
+                if libobs::obs_sceneitem_get_scene(item.get_ptr()) != scene_ptr.get_ptr() {
+                    return Err(ObsError::InvalidOperation(
+                        "Every item of a layout must belong to the scene".to_string(),
+                    ));
+                }
+            }
+
+            let update = LayoutUpdate {
+                items: update
+                    .into_iter()
+                    .filter(|(item, _)| !libobs::obs_sceneitem_locked(item.0))
+                    .collect(),
+            };
+
+            // Safety: The update outlives the call, as the update is applied synchronously.
+            libobs::obs_scene_atomic_update(
+                scene_ptr.get_ptr(),
+                Some(apply_layout_atomic),
+                &update as *const LayoutUpdate as *mut c_void,
+            );
+
+            Ok(())
+        })?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_layout() {
+        let layout = ObsLayout::new(ObsLayoutKind::Grid { columns: None });
+        let cells = layout.compute_cells(1920, 1080, 4).unwrap();
+        assert_eq!(cells.len(), 4);
+        assert_eq!(cells[0], ObsLayoutCell::new(0.0, 0.0, 960.0, 540.0));
+        assert_eq!(cells[3], ObsLayoutCell::new(960.0, 540.0, 960.0, 540.0));
+
+        // The last row with a single item is centered
+        let cells = layout.compute_cells(1920, 1080, 3).unwrap();
+        assert_eq!(cells.len(), 3);
+        assert_eq!(cells[2], ObsLayoutCell::new(480.0, 540.0, 960.0, 540.0));
+    }
+
+    #[test]
+    fn test_grid_layout_padding() {
+        let layout = ObsLayout::new(ObsLayoutKind::Grid { columns: Some(2) }).set_padding(10.0);
+        let cells = layout.compute_cells(1000, 500, 2).unwrap();
+        assert_eq!(cells[0], ObsLayoutCell::new(10.0, 10.0, 485.0, 480.0));
+        assert_eq!(cells[1], ObsLayoutCell::new(505.0, 10.0, 485.0, 480.0));
+    }
+
+    #[test]
+    fn test_picture_in_picture_layout() {
+        let layout = ObsLayout::new(ObsLayoutKind::PictureInPicture {
+            corner: ObsLayoutCorner::BottomRight,
+            size: 0.25,
+            margin: 20.0,
+        });
+
+        let cells = layout.compute_cells(1920, 1080, 2).unwrap();
+        assert_eq!(cells[0], ObsLayoutCell::new(0.0, 0.0, 1920.0, 1080.0));
+        assert_eq!(cells[1], ObsLayoutCell::new(1420.0, 790.0, 480.0, 270.0));
+
+        // An empty canvas has no aspect ratio to scale the small items with
+        assert!(matches!(
+            layout.compute_cells(0, 1080, 2),
+            Err(ObsError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            layout.compute_cells(1920, 0, 2),
+            Err(ObsError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            layout.compute(0, 0, 2),
+            Err(ObsError::InvalidOperation(_))
+        ));
+    }
+
+    #[test]
+    fn test_focus_strip_layout() {
+        let layout = ObsLayout::new(ObsLayoutKind::FocusStrip {
+            edge: ObsLayoutEdge::Bottom,
+            size: 0.25,
+        });
+
+        let cells = layout.compute_cells(1600, 800, 3).unwrap();
+        assert_eq!(cells[0], ObsLayoutCell::new(0.0, 0.0, 1600.0, 600.0));
+        assert_eq!(cells[1], ObsLayoutCell::new(0.0, 600.0, 800.0, 200.0));
+        assert_eq!(cells[2], ObsLayoutCell::new(800.0, 600.0, 800.0, 200.0));
+    }
+}
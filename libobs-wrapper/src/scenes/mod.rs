@@ -6,23 +6,39 @@
 mod transform_info;
 pub use transform_info::*;
 
+mod animation;
+pub use animation::*;
+
+mod layout;
+pub use layout::*;
+
 mod scene_drop_guards;
 mod scene_item;
 
 mod filter_traits;
 pub use filter_traits::*;
 
+mod group;
+pub use group::*;
+
+mod scene_source;
+
+mod collection;
+pub use collection::*;
+
 pub use scene_item::*;
 
 use std::collections::HashMap;
+use std::ffi::c_void;
 use std::fmt::Debug;
 use std::sync::{Arc, RwLock};
 
-use libobs::{obs_scene_t, obs_source_t};
+use libobs::{obs_scene_item, obs_scene_t, obs_source_t};
 
+use crate::enums::{ObsSceneDuplicateType, OsEnumType};
 use crate::macros::impl_eq_of_ptr;
 use crate::scenes::scene_drop_guards::_SceneDropGuard;
-use crate::sources::{ObsFilterGuardPair, ObsSourceTrait};
+use crate::sources::{ObsFilterGuardPair, ObsSourceRef, ObsSourceTrait};
 use crate::unsafe_send::SmartPointerSendable;
 use crate::utils::{GeneralTraitHashMap, ObsDropGuard};
 use crate::{
@@ -45,11 +61,74 @@ pub struct ObsSceneRef {
     attached_filters: Arc<RwLock<Vec<ObsFilterGuardPair>>>,
     runtime: ObsRuntime,
     signals: Arc<ObsSceneSignals>,
+    /// The source of this scene, used when this scene is added to another scene (see `scene_source`).
+    /// It holds its own reference, so the scene stays alive while it is nested in another scene.
+    source: ObsSourceRef,
     scene: SmartPointerSendable<*mut obs_scene_t>,
 }
 
 impl_eq_of_ptr!(ObsSceneRef);
 
+/// Collects the scene items passed to `enum_scene_item` when enumerating a scene or a group.
+struct EnumItemsData {
+    items: Vec<Sendable<*mut obs_scene_item>>,
+    add_ref: bool,
+}
+
+unsafe extern "C" fn enum_scene_item(
+    _scene: *mut obs_scene_t,
+    item: *mut obs_scene_item,
+    param: *mut c_void,
+) -> bool {
+    // Safety: param is always a pointer to EnumItemsData that outlives the enumeration
+    let data = &mut *(param as *mut EnumItemsData);
+    if data.add_ref {
+        // Safety: The item is valid while the scene is enumerated
+        libobs::obs_sceneitem_addref(item);
+    }
+
+    data.items.push(Sendable(item));
+    true
+}
+
+/// Wraps scene items that were referenced while enumerating them, releasing the references
+/// of the remaining items if one of them couldn't be wrapped.
+fn wrap_enumerated_items(
+    scene_ptr: SmartPointerSendable<*mut obs_scene_t>,
+    item_ptrs: Vec<Sendable<*mut obs_scene_item>>,
+    remove_on_drop: bool,
+    runtime: ObsRuntime,
+) -> Result<Vec<ObsSceneItemRef<ObsSourceRef>>, ObsError> {
+    let mut item_ptrs = item_ptrs.into_iter();
+
+    let mut res = Vec::new();
+    while let Some(item_ptr) = item_ptrs.next() {
+        match ObsSceneItemRef::new_from_raw(
+            scene_ptr.clone(),
+            item_ptr,
+            remove_on_drop,
+            runtime.clone(),
+        ) {
+            Ok(item) => res.push(item),
+            Err(e) => {
+                let remaining: Vec<_> = item_ptrs.collect();
+                run_with_obs!(runtime, (remaining), move || {
+                    for item in remaining {
+                        unsafe {
+                            // Safety: We took a reference to every item when enumerating them
+                            libobs::obs_sceneitem_release(item.0);
+                        }
+                    }
+                })?;
+
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(res)
+}
+
 impl ObsSceneRef {
     pub(crate) fn new(name: ObsString, runtime: ObsRuntime) -> Result<Self, ObsError> {
         let scene = run_with_obs!(runtime, (name), move || {
@@ -79,12 +158,31 @@ impl ObsSceneRef {
             Ok(Sendable(scene_ptr))
         })??;
 
+        Self::new_from_raw(name, scene, runtime)
+    }
+
+    /// Wraps a scene that was created by OBS, for example by duplicating another scene.
+    /// This takes ownership of the reference to the scene, which is released once every clone of the returned struct is dropped.
+    pub(crate) fn new_from_raw(
+        name: ObsString,
+        scene: Sendable<*mut obs_scene_t>,
+        runtime: ObsRuntime,
+    ) -> Result<Self, ObsError> {
         let drop_guard = Arc::new(_SceneDropGuard::new(scene.clone(), runtime.clone()));
         let scene = SmartPointerSendable::new(scene.0, drop_guard);
 
+        let scene_ptr = scene.clone();
+        let source_ptr = run_with_obs!(runtime, (scene_ptr), move || unsafe {
+            // Safety: The scene pointer is valid because of the smart pointer
+            Sendable(libobs::obs_scene_get_source(scene_ptr.get_ptr()))
+        })?;
+
+        let source = ObsSourceRef::new_from_raw(source_ptr, runtime.clone())?;
+
         let signals = Arc::new(ObsSceneSignals::new(&scene, runtime.clone())?);
         Ok(Self {
             name,
+            source,
             scene,
             attached_scene_items: Arc::new(RwLock::new(HashMap::new())),
             attached_filters: Arc::new(RwLock::new(Vec::new())),
@@ -93,6 +191,65 @@ impl ObsSceneRef {
         })
     }
 
+    /// Creates a copy of this scene with the given name, for example to use this scene as a template.
+    /// See `ObsSceneDuplicateType` for how the sources of this scene are duplicated.
+    ///
+    /// Every item of the new scene is stored in it, just like sources added by `add_source`,
+    /// with its source wrapped as `ObsSourceRef`. Unlike scenes created by `ObsContext::scene`,
+    /// the new scene is not stored in the context and is released once every clone of it is dropped.
+    pub fn duplicate<T: Into<ObsString>>(
+        &self,
+        name: T,
+        duplicate_type: ObsSceneDuplicateType,
+    ) -> Result<ObsSceneRef, ObsError> {
+        let name: ObsString = name.into();
+        let scene_ptr = self.scene.clone();
+
+        let duplicated = run_with_obs!(self.runtime, (scene_ptr, name), move || {
+            let duplicated = unsafe {
+                // Safety: The scene pointer is valid because of the smart pointer and the name is owned by this closure
+                libobs::obs_scene_duplicate(
+                    scene_ptr.get_ptr(),
+                    name.as_ptr().0,
+                    duplicate_type as OsEnumType,
+                )
+            };
+
+            if duplicated.is_null() {
+                Err(ObsError::NullPointer(None))
+            } else {
+                Ok(Sendable(duplicated))
+            }
+        })??;
+
+        let scene = ObsSceneRef::new_from_raw(name, duplicated, self.runtime.clone())?;
+
+        scene.attach_existing_items()?;
+        Ok(scene)
+    }
+
+    /// Stores every item that is already in this scene, for example after duplicating or loading it,
+    /// just like items added with `add_source`. Their sources are wrapped as `ObsSourceRef`.
+    pub(crate) fn attach_existing_items(&self) -> Result<(), ObsError> {
+        // These items belong to this scene, so they are removed once they are dropped
+        let item_ptrs = self.enum_item_ptrs(true)?;
+        let items = wrap_enumerated_items(self.as_ptr(), item_ptrs, true, self.runtime.clone())?;
+
+        let mut attached = self
+            .attached_scene_items
+            .write()
+            .map_err(|e| ObsError::LockError(format!("{:?}", e)))?;
+
+        for item in items {
+            attached
+                .entry(Arc::new(Box::new(item.inner_source().clone())))
+                .or_insert_with(Vec::new)
+                .push(Arc::new(Box::new(item)));
+        }
+
+        Ok(())
+    }
+
     #[deprecated = "Use ObsSceneRef::set_to_channel instead"]
     pub fn add_and_set(&self, channel: u32) -> Result<(), ObsError> {
         self.set_to_channel(channel)
@@ -157,6 +314,35 @@ impl ObsSceneRef {
     pub fn signals(&self) -> Arc<ObsSceneSignals> {
         self.signals.clone()
     }
+
+    /// Returns the pointers of every item in this scene, ordered from bottom to top.
+    ///
+    /// If `add_ref` is `true`, a reference is taken for every item, which must be released by the caller.
+    /// Otherwise the pointers are only valid as long as the items stay in the scene.
+    pub(crate) fn enum_item_ptrs(
+        &self,
+        add_ref: bool,
+    ) -> Result<Vec<Sendable<*mut obs_scene_item>>, ObsError> {
+        let scene_ptr = self.scene.clone();
+        run_with_obs!(self.runtime, (scene_ptr), move || {
+            let mut data = EnumItemsData {
+                items: Vec::new(),
+                add_ref,
+            };
+
+            unsafe {
+                // Safety: The scene pointer is valid because of the smart pointer and the data
+                // outlives the call, as the items are enumerated synchronously.
+                libobs::obs_scene_enum_items(
+                    scene_ptr.get_ptr(),
+                    Some(enum_scene_item),
+                    &mut data as *mut _ as *mut c_void,
+                );
+            }
+
+            data.items
+        })
+    }
 }
 
 impl_signal_manager!(|scene_ptr: SmartPointerSendable<*mut obs_scene_t>| unsafe {
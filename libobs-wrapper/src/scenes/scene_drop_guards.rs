@@ -42,8 +42,9 @@ impl_obs_drop!(_SceneDropGuard, (scene), move || {
     }
 
     unsafe {
-        // Safety: We are in the runtime and the pointer is valid because of the drop guard
-        libobs::obs_source_release(scene_source);
+        // Safety: We are in the runtime and the pointer is valid because of the drop guard.
+        // The scene and its source share the same reference, so it must only be released once.
+        // Other scenes this scene was added to hold their own reference and keep it alive.
         libobs::obs_scene_release(scene.0);
     }
 });
@@ -3,21 +3,28 @@
 mod traits;
 pub use traits::SceneItemExtSceneTrait;
 
-use std::{fmt::Debug, hash::Hash, sync::Arc};
+use std::{ffi::CStr, fmt::Debug, hash::Hash, str::FromStr, sync::Arc};
 
 use libobs::{obs_scene_item, obs_transform_info, obs_video_info};
 
 use crate::{
-    enums::ObsBoundsType,
-    graphics::Vec2,
+    data::{ImmutableObsData, ObsDataPointers},
+    enums::{
+        ObsBlendingMethod, ObsBlendingMode, ObsBoundsType, ObsOrderMovement, ObsScaleType,
+        OsEnumType,
+    },
+    graphics::{Mat4, OrientedBox, Vec2},
     impl_obs_drop,
-    macros::trait_with_optional_send_sync,
+    macros::{enum_from_number, trait_with_optional_send_sync},
     run_with_obs,
     runtime::ObsRuntime,
-    scenes::{ObsSceneRef, ObsTransformInfo, ObsTransformInfoBuilder},
-    sources::ObsSourceTrait,
+    scenes::{
+        ItemLockedSignal, ItemVisibleSignal, ObsAnimationHandle, ObsSceneItemAnimation,
+        ObsSceneItemCrop, ObsSceneRef, ObsTransformInfo, ObsTransformInfoBuilder,
+    },
+    sources::{ObsSceneItemTransition, ObsSourceRef, ObsSourceTrait, ObsTransitionType},
     unsafe_send::{Sendable, SmartPointerSendable},
-    utils::{ObsDropGuard, ObsError},
+    utils::{ObsDropGuard, ObsError, ObsString},
 };
 
 #[derive(Debug)]
@@ -34,6 +41,31 @@ impl_obs_drop!(_ObsSceneItemDropGuard, (scene_item), move || unsafe {
     // Release is called under the hood
 });
 
+/// Used for scene items that were not created by `ObsSceneItemRef::new`, for example when enumerating
+/// the items of a scene. Dropping it releases our reference and only removes the item from its scene
+/// if `remove` is set.
+#[derive(Debug)]
+pub(super) struct _ObsSceneItemReleaseGuard {
+    scene_item: Sendable<*mut obs_scene_item>,
+    remove: bool,
+    runtime: ObsRuntime,
+}
+
+impl ObsDropGuard for _ObsSceneItemReleaseGuard {}
+impl_obs_drop!(
+    _ObsSceneItemReleaseGuard,
+    (scene_item, remove),
+    move || unsafe {
+        // Safety: We hold a reference to the scene item, so the pointer is valid.
+        // Removing an item twice is fine, as OBS checks whether it was removed already.
+        if remove {
+            libobs::obs_sceneitem_remove(scene_item.0);
+        }
+
+        libobs::obs_sceneitem_release(scene_item.0);
+    }
+);
+
 #[derive(Debug, Clone)]
 /// Holds the specific source that was added to the scene and its scene item.
 /// If this struct is attached to the scene, it'll not be dropped as the scene
@@ -92,6 +124,48 @@ impl<T: ObsSourceTrait + Clone> ObsSceneItemRef<T> {
     }
 }
 
+impl ObsSceneItemRef<ObsSourceRef> {
+    /// Wraps a scene item that already exists in the given scene and resolves its source.
+    /// The caller must pass a referenced scene item, which is released once this struct is dropped.
+    /// Unlike scene items created by `ObsSceneItemRef::new`, the item is only removed from
+    /// its scene on drop if `remove_on_drop` is set.
+    pub(crate) fn new_from_raw(
+        scene_ptr: SmartPointerSendable<*mut libobs::obs_scene>,
+        scene_item: Sendable<*mut obs_scene_item>,
+        remove_on_drop: bool,
+        runtime: ObsRuntime,
+    ) -> Result<Self, ObsError> {
+        let drop_guard = _ObsSceneItemReleaseGuard {
+            scene_item: scene_item.clone(),
+            remove: remove_on_drop,
+            runtime: runtime.clone(),
+        };
+
+        let scene_item_ptr = SmartPointerSendable::new(scene_item.0, Arc::new(drop_guard));
+
+        let item_ptr = scene_item_ptr.clone();
+        let source_ptr = run_with_obs!(runtime, (item_ptr), move || unsafe {
+            // Safety: The pointer is valid as it is a safe pointer
+            Sendable(libobs::obs_sceneitem_get_source(item_ptr.get_ptr()))
+        })?;
+
+        if source_ptr.0.is_null() {
+            return Err(ObsError::NullPointer(Some(
+                "Scene item has no source".into(),
+            )));
+        }
+
+        let source = ObsSourceRef::new_from_raw(source_ptr, runtime.clone())?;
+
+        Ok(Self {
+            underlying_source: source,
+            _scene_ptr: scene_ptr,
+            scene_item_ptr,
+            runtime,
+        })
+    }
+}
+
 trait_with_optional_send_sync! {
     pub trait SceneItemTrait: Debug {
         fn as_ptr(&self) -> &SmartPointerSendable<*mut obs_scene_item>;
@@ -118,6 +192,26 @@ trait_with_optional_send_sync! {
             Ok(item_info)
         }
 
+        /// Gets the transform that maps the unit square onto the box of this item in its scene (or group).
+        /// It includes the position, scale, rotation, crop and bounds of this item.
+        fn get_box_transform(&self) -> Result<Mat4, ObsError> {
+            let self_ptr = self.as_ptr();
+            run_with_obs!(self.runtime(), (self_ptr), move || {
+                Mat4::read_with(|transform| unsafe {
+                    // Safety: The pointer is valid as it is a safe pointer. The transform is only updated
+                    // on the next video tick by default, so we are forcing the update here.
+                    libobs::obs_sceneitem_force_update_transform(self_ptr.get_ptr());
+                    libobs::obs_sceneitem_get_box_transform(self_ptr.get_ptr(), transform);
+                })
+            })
+        }
+
+        /// Gets the box of this item in its scene (or group), which is rotated with the item.
+        /// Use `OrientedBox::bounding_rect` to get the area covered by this item.
+        fn get_oriented_box(&self) -> Result<OrientedBox, ObsError> {
+            Ok(OrientedBox::from_transform(self.get_box_transform()?))
+        }
+
         /// Gets the position of the given source in this scene.
         fn get_source_position(&self) -> Result<Vec2, ObsError> {
             let self_ptr = self.as_ptr();
@@ -192,6 +286,15 @@ trait_with_optional_send_sync! {
             Ok(())
         }
 
+        /// Starts a keyframe animation of the transform of this scene item.
+        /// The animation is stopped when the returned handle is dropped.
+        fn animate(&self, animation: &ObsSceneItemAnimation) -> Result<ObsAnimationHandle, ObsError>
+        where
+            Self: Sized,
+        {
+            animation.start(self)
+        }
+
         /// Fits the given source to the screen size.
         /// If the source is locked, no action is taken.
         ///
@@ -262,6 +365,319 @@ trait_with_optional_send_sync! {
 
             Ok(())
         }
+
+        /// Gets the unique id of this scene item within its scene.
+        fn get_id(&self) -> Result<i64, ObsError> {
+            let self_ptr = self.as_ptr();
+            run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_get_id(self_ptr.get_ptr())
+            })
+        }
+
+        /// Checks whether this scene item is a group (see `ObsSceneGroupRef`).
+        fn is_group(&self) -> Result<bool, ObsError> {
+            let self_ptr = self.as_ptr();
+            run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_is_group(self_ptr.get_ptr())
+            })
+        }
+
+        /// Gets the crop of the given source in this scene.
+        fn get_crop(&self) -> Result<ObsSceneItemCrop, ObsError> {
+            let self_ptr = self.as_ptr();
+            let crop = run_with_obs!(self.runtime(), (self_ptr), move || {
+                let crop = unsafe {
+                    // Safety: this is safe to call because we a filling a struct with zeros
+                    let mut crop: libobs::obs_sceneitem_crop = std::mem::zeroed();
+
+                    // Safety: Fill the crop struct with the crop data
+                    libobs::obs_sceneitem_get_crop(self_ptr.get_ptr(), &mut crop);
+
+                    crop
+                };
+
+                ObsSceneItemCrop::from(crop)
+            })?;
+
+            Ok(crop)
+        }
+
+        /// Sets the crop of the given source in this scene.
+        fn set_crop(&self, crop: ObsSceneItemCrop) -> Result<(), ObsError> {
+            let self_ptr = self.as_ptr();
+
+            run_with_obs!(self.runtime(), (self_ptr), move || {
+                let crop: libobs::obs_sceneitem_crop = crop.into();
+
+                unsafe {
+                    // Safety: The pointer is valid as it is a safe pointer
+                    libobs::obs_sceneitem_set_crop(self_ptr.get_ptr(), &crop);
+                }
+            })?;
+
+            Ok(())
+        }
+
+        /// Checks whether the given source is visible in this scene.
+        fn is_visible(&self) -> Result<bool, ObsError> {
+            let self_ptr = self.as_ptr();
+            run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_visible(self_ptr.get_ptr())
+            })
+        }
+
+        /// Shows or hides the given source in this scene, playing the show or hide transition if one is set (see `set_transition`).
+        /// This emits the `item_visible` signal of the scene (see `ItemVisibleSignal`) if the visibility changed.
+        fn set_visible(&self, visible: bool) -> Result<(), ObsError> {
+            let self_ptr = self.as_ptr();
+            run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_set_visible(self_ptr.get_ptr(), visible);
+            })
+        }
+
+        /// Checks whether the given source is locked in this scene.
+        fn is_locked(&self) -> Result<bool, ObsError> {
+            let self_ptr = self.as_ptr();
+            run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_locked(self_ptr.get_ptr())
+            })
+        }
+
+        /// Locks or unlocks the given source in this scene. Locked items can't be moved in the OBS editor
+        /// and are not resized by `fit_source_to_screen`.
+        /// This emits the `item_locked` signal of the scene (see `ItemLockedSignal`) if the lock state changed.
+        fn set_locked(&self, locked: bool) -> Result<(), ObsError> {
+            let self_ptr = self.as_ptr();
+            run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_set_locked(self_ptr.get_ptr(), locked);
+            })
+        }
+
+        /// Gets the blending mode of the given source in this scene.
+        fn get_blending_mode(&self) -> Result<ObsBlendingMode, ObsError> {
+            let self_ptr = self.as_ptr();
+            let mode = run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_get_blending_mode(self_ptr.get_ptr())
+            })?;
+
+            enum_from_number!(ObsBlendingMode, mode).ok_or_else(|| {
+                ObsError::EnumConversionError(format!("Unknown blending mode {}", mode))
+            })
+        }
+
+        /// Sets the blending mode of the given source in this scene.
+        fn set_blending_mode(&self, mode: ObsBlendingMode) -> Result<(), ObsError> {
+            let self_ptr = self.as_ptr();
+            run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_set_blending_mode(self_ptr.get_ptr(), mode as OsEnumType);
+            })
+        }
+
+        /// Gets the blending method of the given source in this scene.
+        fn get_blending_method(&self) -> Result<ObsBlendingMethod, ObsError> {
+            let self_ptr = self.as_ptr();
+            let method = run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_get_blending_method(self_ptr.get_ptr())
+            })?;
+
+            enum_from_number!(ObsBlendingMethod, method).ok_or_else(|| {
+                ObsError::EnumConversionError(format!("Unknown blending method {}", method))
+            })
+        }
+
+        /// Sets the blending method of the given source in this scene.
+        fn set_blending_method(&self, method: ObsBlendingMethod) -> Result<(), ObsError> {
+            let self_ptr = self.as_ptr();
+            run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_set_blending_method(self_ptr.get_ptr(), method as OsEnumType);
+            })
+        }
+
+        /// Gets the filter that is used to scale the given source in this scene.
+        fn get_scale_filter(&self) -> Result<ObsScaleType, ObsError> {
+            let self_ptr = self.as_ptr();
+            let filter = run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_get_scale_filter(self_ptr.get_ptr())
+            })?;
+
+            enum_from_number!(ObsScaleType, filter).ok_or_else(|| {
+                ObsError::EnumConversionError(format!("Unknown scale filter {}", filter))
+            })
+        }
+
+        /// Sets the filter that is used to scale the given source in this scene.
+        fn set_scale_filter(&self, filter: ObsScaleType) -> Result<(), ObsError> {
+            let self_ptr = self.as_ptr();
+            run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_set_scale_filter(self_ptr.get_ptr(), filter as OsEnumType);
+            })
+        }
+
+        /// Sets the transition that is played when the given source is shown or hidden in this scene,
+        /// for example by `set_visible`. Passing `None` removes the transition, so the source appears
+        /// or disappears instantly. Use `set_transition_duration` to set how long the transition takes.
+        fn set_transition(
+            &self,
+            kind: ObsSceneItemTransition,
+            transition: Option<ObsTransitionType>,
+            settings: Option<ImmutableObsData>,
+        ) -> Result<(), ObsError> {
+            let self_ptr = self.as_ptr();
+            let id = transition.map(ObsString::from);
+            let name = ObsString::new(match kind {
+                ObsSceneItemTransition::Show => "Show transition",
+                ObsSceneItemTransition::Hide => "Hide transition",
+            });
+            let settings_ptr = settings.as_ref().map(|s| s.as_ptr());
+
+            run_with_obs!(self.runtime(), (self_ptr, id, name, settings_ptr), move || {
+                let transition_ptr = match &id {
+                    Some(id) => {
+                        let settings_raw_ptr = match &settings_ptr {
+                            Some(s) => s.get_ptr(),
+                            None => std::ptr::null_mut(),
+                        };
+
+                        let ptr = unsafe {
+                            // Safety: The id and name are owned by this closure and the settings may be null.
+                            libobs::obs_source_create_private(
+                                id.as_ptr().0,
+                                name.as_ptr().0,
+                                settings_raw_ptr,
+                            )
+                        };
+
+                        if ptr.is_null() {
+                            return Err(ObsError::NullPointer(Some(format!(
+                                "Couldn't create transition {}",
+                                id
+                            ))));
+                        }
+
+                        ptr
+                    }
+                    None => std::ptr::null_mut(),
+                };
+
+                unsafe {
+                    // Safety: The pointer is valid as it is a safe pointer. The scene item takes its own reference
+                    // to the transition, so we are releasing ours afterwards.
+                    libobs::obs_sceneitem_set_transition(
+                        self_ptr.get_ptr(),
+                        kind.is_show(),
+                        transition_ptr,
+                    );
+
+                    if !transition_ptr.is_null() {
+                        libobs::obs_source_release(transition_ptr);
+                    }
+                }
+
+                Ok(())
+            })?
+        }
+
+        /// Gets the type of the transition that is played when the given source is shown or hidden in this scene.
+        fn get_transition(
+            &self,
+            kind: ObsSceneItemTransition,
+        ) -> Result<Option<ObsTransitionType>, ObsError> {
+            let self_ptr = self.as_ptr();
+            let id = run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer and the transition
+                // is kept alive by the scene item while we copy its id.
+                let transition = libobs::obs_sceneitem_get_transition(self_ptr.get_ptr(), kind.is_show());
+                if transition.is_null() {
+                    return None;
+                }
+
+                let id = libobs::obs_source_get_id(transition);
+                if id.is_null() {
+                    return None;
+                }
+
+                Some(CStr::from_ptr(id).to_string_lossy().to_string())
+            })?;
+
+            Ok(id.map(|id| ObsTransitionType::from_str(&id).unwrap()))
+        }
+
+        /// Sets how long the show or hide transition of the given source takes, in milliseconds.
+        fn set_transition_duration(
+            &self,
+            kind: ObsSceneItemTransition,
+            duration_ms: u32,
+        ) -> Result<(), ObsError> {
+            let self_ptr = self.as_ptr();
+            run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_set_transition_duration(
+                    self_ptr.get_ptr(),
+                    kind.is_show(),
+                    duration_ms,
+                );
+            })
+        }
+
+        /// Gets how long the show or hide transition of the given source takes, in milliseconds.
+        fn get_transition_duration(&self, kind: ObsSceneItemTransition) -> Result<u32, ObsError> {
+            let self_ptr = self.as_ptr();
+            run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_get_transition_duration(self_ptr.get_ptr(), kind.is_show())
+            })
+        }
+
+        /// Gets the position of the given source within the order of this scene.
+        /// Position `0` is the bottom-most item, which is rendered below every other item.
+        fn get_order_position(&self) -> Result<usize, ObsError> {
+            let self_ptr = self.as_ptr();
+            let position = run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_get_order_position(self_ptr.get_ptr())
+            })?;
+
+            usize::try_from(position).map_err(|_| {
+                ObsError::InvalidOperation(format!("Invalid order position {}", position))
+            })
+        }
+
+        /// Moves the given source up, down, to the top or to the bottom of this scene.
+        /// This emits the `reorder` signal of the scene.
+        fn set_order(&self, movement: ObsOrderMovement) -> Result<(), ObsError> {
+            let self_ptr = self.as_ptr();
+            run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_set_order(self_ptr.get_ptr(), movement as OsEnumType);
+            })
+        }
+
+        /// Moves the given source to an absolute position within the order of this scene,
+        /// where `0` is the bottom-most position. Positions past the top are clamped by OBS.
+        /// This emits the `reorder` signal of the scene.
+        fn set_order_position(&self, position: usize) -> Result<(), ObsError> {
+            let position = std::os::raw::c_int::try_from(position).map_err(|_| {
+                ObsError::InvalidOperation(format!("Order position {} is too large", position))
+            })?;
+
+            let self_ptr = self.as_ptr();
+            run_with_obs!(self.runtime(), (self_ptr), move || unsafe {
+                // Safety: The pointer is valid as it is a safe pointer
+                libobs::obs_sceneitem_set_order_position(self_ptr.get_ptr(), position);
+            })
+        }
     }
 }
 
@@ -298,6 +714,20 @@ where
     }
 }
 
+impl ItemVisibleSignal {
+    /// Checks whether this signal was emitted for the given scene item.
+    pub fn is_for<K: SceneItemTrait + ?Sized>(&self, scene_item: &K) -> bool {
+        self.item.0 == scene_item.as_ptr().get_ptr()
+    }
+}
+
+impl ItemLockedSignal {
+    /// Checks whether this signal was emitted for the given scene item.
+    pub fn is_for<K: SceneItemTrait + ?Sized>(&self, scene_item: &K) -> bool {
+        self.item.0 == scene_item.as_ptr().get_ptr()
+    }
+}
+
 // The macro doesn't support generics yet, so we implement it manually
 //impl_eq_of_ptr!(SceneItemRef<T>, scene_item_ptr);
 
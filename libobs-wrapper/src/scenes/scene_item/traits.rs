@@ -1,4 +1,6 @@
-use crate::scenes::{ObsSceneItemRef, ObsSceneRef, SceneItemTrait};
+use crate::graphics::{Mat4, OrientedBox, Vec2};
+use crate::run_with_obs;
+use crate::scenes::{wrap_enumerated_items, ObsSceneItemRef, ObsSceneRef, SceneItemTrait};
 use crate::sources::{ObsSourceRef, ObsSourceTrait};
 use crate::utils::{ObsError, SourceInfo};
 use std::sync::Arc;
@@ -6,6 +8,9 @@ use std::sync::Arc;
 pub trait SceneItemExtSceneTrait {
     /// Adds the specified source to this scene. Returns a reference to the created scene item.
     /// You can use that SceneItemPtr to manipulate the source within this scene (position, scale, rotation, etc).
+    ///
+    /// Other scenes can be added as well, as long as this doesn't nest this scene within itself.
+    /// The added scene is kept alive by this scene, even if every other `ObsSceneRef` of it is dropped.
     fn add_source<T: ObsSourceTrait + Clone + 'static>(
         &mut self,
         source: T,
@@ -38,6 +43,25 @@ pub trait SceneItemExtSceneTrait {
         &self,
         source: &T,
     ) -> Result<Vec<Arc<Box<dyn SceneItemTrait>>>, ObsError>;
+
+    /// Gets every scene item that was added to this scene, ordered from bottom to top
+    /// as they are rendered. Use `SceneItemTrait::set_order` or `SceneItemTrait::set_order_position`
+    /// to change the order.
+    fn get_ordered_scene_items(&self) -> Result<Vec<Arc<Box<dyn SceneItemTrait>>>, ObsError>;
+
+    /// Gets every item of this scene directly from OBS, ordered from bottom to top.
+    ///
+    /// Unlike `get_ordered_scene_items`, this includes items that were not added through `add_source`,
+    /// for example items of scenes loaded from a scene collection or added by plugins.
+    /// The returned items are not stored in this scene and dropping them keeps the items in the scene.
+    fn enum_scene_items(&self) -> Result<Vec<ObsSceneItemRef<ObsSourceRef>>, ObsError>;
+
+    /// Finds the topmost visible item of this scene whose box contains the given point,
+    /// taking rotation, crop and bounds into account (see `SceneItemTrait::get_oriented_box`).
+    ///
+    /// Items within groups are not checked individually, the group itself is returned instead.
+    /// The returned item is not stored in this scene and dropping it keeps the item in the scene.
+    fn item_at(&self, point: Vec2) -> Result<Option<ObsSceneItemRef<ObsSourceRef>>, ObsError>;
 }
 
 impl SceneItemExtSceneTrait for ObsSceneRef {
@@ -45,6 +69,14 @@ impl SceneItemExtSceneTrait for ObsSceneRef {
         &mut self,
         source: T,
     ) -> Result<ObsSceneItemRef<T>, ObsError> {
+        if self.would_create_cycle(source.as_ptr())? {
+            return Err(ObsError::InvalidOperation(format!(
+                "Adding {} to scene {} would nest the scene within itself",
+                source.name(),
+                self.name
+            )));
+        }
+
         let scene_item = ObsSceneItemRef::new(self, source.clone(), self.runtime.clone())?;
 
         let scene_clone = scene_item.clone();
@@ -152,4 +184,71 @@ impl SceneItemExtSceneTrait for ObsSceneRef {
 
         Ok(res)
     }
+
+    fn get_ordered_scene_items(&self) -> Result<Vec<Arc<Box<dyn SceneItemTrait>>>, ObsError> {
+        let order = self.enum_item_ptrs(false)?;
+        let position_of = |item: &Arc<Box<dyn SceneItemTrait>>| {
+            let ptr = item.as_ptr().get_ptr();
+            order.iter().position(|p| p.0 == ptr).unwrap_or(usize::MAX)
+        };
+
+        let mut res: Vec<_> = self
+            .attached_scene_items
+            .read()
+            .map_err(|e| ObsError::LockError(format!("{:?}", e)))?
+            .values()
+            .flatten()
+            .cloned()
+            .collect();
+
+        res.sort_by_key(position_of);
+        Ok(res)
+    }
+
+    fn enum_scene_items(&self) -> Result<Vec<ObsSceneItemRef<ObsSourceRef>>, ObsError> {
+        let item_ptrs = self.enum_item_ptrs(true)?;
+        wrap_enumerated_items(self.as_ptr(), item_ptrs, false, self.runtime.clone())
+    }
+
+    fn item_at(&self, point: Vec2) -> Result<Option<ObsSceneItemRef<ObsSourceRef>>, ObsError> {
+        let item_ptrs = self.enum_item_ptrs(true)?;
+        let hit = run_with_obs!(self.runtime, (item_ptrs), move || {
+            let mut hit = None;
+
+            // Items are enumerated from bottom to top, so the topmost item is checked first
+            for item in item_ptrs.into_iter().rev() {
+                let contains = hit.is_none()
+                    && unsafe {
+                        // Safety: We took a reference to every item when enumerating them
+                        libobs::obs_sceneitem_visible(item.0)
+                    }
+                    && OrientedBox::from_transform(Mat4::read_with(|transform| unsafe {
+                        // Safety: The transform is only updated on the next video tick by default,
+                        // so we are forcing the update here.
+                        libobs::obs_sceneitem_force_update_transform(item.0);
+                        libobs::obs_sceneitem_get_box_transform(item.0, transform);
+                    }))
+                    .contains(point);
+
+                if contains {
+                    hit = Some(item);
+                } else {
+                    unsafe {
+                        // Safety: We took a reference to every item when enumerating them
+                        libobs::obs_sceneitem_release(item.0);
+                    }
+                }
+            }
+
+            hit
+        })?;
+
+        let Some(hit) = hit else {
+            return Ok(None);
+        };
+
+        let mut items =
+            wrap_enumerated_items(self.as_ptr(), vec![hit], false, self.runtime.clone())?;
+        Ok(items.pop())
+    }
 }
@@ -0,0 +1,138 @@
+//! Allows scenes to be used as sources, so a scene can be added to another scene using `add_source`.
+
+use std::{ffi::c_void, sync::Arc};
+
+use libobs::{obs_scene_t, obs_source_t};
+
+use crate::{
+    data::{
+        object::{ObsObjectTrait, ObsObjectTraitPrivate},
+        ImmutableObsData, ObsData,
+    },
+    run_with_obs,
+    runtime::ObsRuntime,
+    sources::{ObsFilterGuardPair, ObsFilterRef, ObsSourceSignals, ObsSourceTrait},
+    unsafe_send::SmartPointerSendable,
+    utils::{ObsError, ObsString},
+};
+
+use super::{enum_scene_item, EnumItemsData, ObsSceneRef};
+
+/// Checks whether `target` is `scene` itself or is nested somewhere within `scene`, including groups.
+///
+/// # Safety
+/// Must be called on the OBS thread and `scene` must be a valid pointer.
+unsafe fn scene_contains_source(scene: *mut obs_scene_t, target: *mut obs_source_t) -> bool {
+    if libobs::obs_scene_get_source(scene) == target {
+        return true;
+    }
+
+    let mut data = EnumItemsData {
+        items: Vec::new(),
+        add_ref: true,
+    };
+
+    // Safety: The data outlives the call, as the items are enumerated synchronously.
+    libobs::obs_scene_enum_items(
+        scene,
+        Some(enum_scene_item),
+        &mut data as *mut _ as *mut c_void,
+    );
+
+    let mut found = false;
+    for item in data.items {
+        if !found {
+            // Safety: We hold a reference to the item, so it and its source are valid
+            let source = libobs::obs_sceneitem_get_source(item.0);
+            let mut nested = libobs::obs_scene_from_source(source);
+            if nested.is_null() {
+                nested = libobs::obs_group_from_source(source);
+            }
+
+            found = !nested.is_null() && scene_contains_source(nested, target);
+        }
+
+        // Safety: The reference was taken when enumerating the items
+        libobs::obs_sceneitem_release(item.0);
+    }
+
+    found
+}
+
+impl ObsSceneRef {
+    /// Checks whether adding the given source to this scene would nest this scene within itself,
+    /// either directly or through other scenes and groups.
+    pub(super) fn would_create_cycle(
+        &self,
+        source: SmartPointerSendable<*mut obs_source_t>,
+    ) -> Result<bool, ObsError> {
+        let scene_source_ptr = self.get_scene_source_ptr()?;
+        run_with_obs!(self.runtime, (source, scene_source_ptr), move || unsafe {
+            // Safety: The source is valid because of the smart pointer and we are on the OBS thread
+            let nested = libobs::obs_scene_from_source(source.get_ptr());
+            if nested.is_null() {
+                return false;
+            }
+
+            scene_contains_source(nested, scene_source_ptr.0)
+        })
+    }
+}
+
+impl ObsObjectTraitPrivate for ObsSceneRef {
+    fn __internal_replace_settings(&self, settings: ImmutableObsData) -> Result<(), ObsError> {
+        self.source.__internal_replace_settings(settings)
+    }
+
+    fn __internal_replace_hotkey_data(
+        &self,
+        hotkey_data: ImmutableObsData,
+    ) -> Result<(), ObsError> {
+        self.source.__internal_replace_hotkey_data(hotkey_data)
+    }
+}
+
+impl ObsObjectTrait<*mut obs_source_t> for ObsSceneRef {
+    fn runtime(&self) -> &ObsRuntime {
+        &self.runtime
+    }
+
+    fn settings(&self) -> Result<ImmutableObsData, ObsError> {
+        self.source.settings()
+    }
+
+    fn hotkey_data(&self) -> Result<ImmutableObsData, ObsError> {
+        self.source.hotkey_data()
+    }
+
+    fn id(&self) -> ObsString {
+        self.source.id()
+    }
+
+    fn name(&self) -> ObsString {
+        self.name.clone()
+    }
+
+    fn update_settings(&self, settings: ObsData) -> Result<(), ObsError> {
+        self.source.update_settings(settings)
+    }
+
+    /// Returns the source of this scene. Use `ObsSceneRef::as_ptr` to get the scene itself.
+    fn as_ptr(&self) -> SmartPointerSendable<*mut obs_source_t> {
+        ObsObjectTrait::as_ptr(&self.source)
+    }
+}
+
+impl ObsSourceTrait for ObsSceneRef {
+    fn signals(&self) -> &Arc<ObsSourceSignals> {
+        ObsSourceTrait::signals(&self.source)
+    }
+
+    fn get_active_filters(&self) -> Result<Vec<ObsFilterGuardPair>, ObsError> {
+        self.source.get_active_filters()
+    }
+
+    fn apply_filter(&self, filter: &ObsFilterRef) -> Result<(), ObsError> {
+        self.source.apply_filter(filter)
+    }
+}
@@ -1,6 +1,7 @@
 use std::fmt::Debug;
 
-use libobs::obs_transform_info;
+use getters0::Getters;
+use libobs::{obs_sceneitem_crop, obs_transform_info};
 
 use crate::{
     enums::{ObsAlignment, ObsBoundsType, OsEnumType},
@@ -76,6 +77,41 @@ impl ObsTransformInfo {
     }
 }
 
+/// The amount of pixels cropped from each side of a scene item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Getters)]
+pub struct ObsSceneItemCrop {
+    #[get_mut]
+    left: i32,
+    #[get_mut]
+    top: i32,
+    #[get_mut]
+    right: i32,
+    #[get_mut]
+    bottom: i32,
+}
+
+impl From<obs_sceneitem_crop> for ObsSceneItemCrop {
+    fn from(raw: obs_sceneitem_crop) -> Self {
+        Self {
+            left: raw.left,
+            top: raw.top,
+            right: raw.right,
+            bottom: raw.bottom,
+        }
+    }
+}
+
+impl From<ObsSceneItemCrop> for obs_sceneitem_crop {
+    fn from(val: ObsSceneItemCrop) -> Self {
+        obs_sceneitem_crop {
+            left: val.left,
+            top: val.top,
+            right: val.right,
+            bottom: val.bottom,
+        }
+    }
+}
+
 pub struct ObsTransformInfoBuilder {
     pos: Option<Vec2>,
     scale: Option<Vec2>,
@@ -1,8 +1,11 @@
 //! Signals can be emitted by sources attached to a scene. You may implement your own signal manager
 //! by using the `impl_signal_manager` macro, but you'll need to make sure that you know which signals are emitted and what structure they have.
 mod handler;
+mod registry;
 mod traits;
 
+#[doc(hidden)]
+pub use registry::*;
 pub use traits::*;
 
 /// Generates a signal manager for OBS objects that can emit signals.
@@ -37,6 +40,11 @@ pub use traits::*;
 /// - Automatic signal handler registration and cleanup
 /// - Thread-safe signal dispatching using `tokio::sync::broadcast`
 ///
+/// Managers of the same object share their senders, as OBS only passes the raw pointer of the object
+/// to the signal handlers. The handlers are connected by the first manager of an object and
+/// disconnected once the last one is dropped, so wrapping an object a second time (for example
+/// when enumerating the items of a scene) doesn't affect the receivers of the first wrapper.
+///
 /// # Signal Data Types
 ///
 /// Signals can carry different types of data:
@@ -154,26 +162,26 @@ macro_rules! impl_signal_manager {
                 let _ = senders.send(res);
             })*
 
+            lazy_static::lazy_static! {
+                /// The managers of every object, see `SignalManagerRegistry`.
+                static ref [<$name:snake:upper _REGISTRY>]: $crate::signals::SignalManagerRegistry = $crate::signals::SignalManagerRegistry::new();
+            }
+
             /// This signal manager must be within an `Arc` if you want to clone it.
+            /// Multiple managers of the same object share their senders, so receivers keep working
+            /// as long as any manager of the object exists.
             #[derive(Debug)]
             pub struct $name {
                 runtime: $crate::runtime::ObsRuntime,
                 pointer: $crate::unsafe_send::SmartPointerSendable<$ptr>,
             }
 
-            impl $name {
-                fn smart_ptr_to_key(ptr: &$crate::unsafe_send::SmartPointerSendable<$ptr>) -> usize {
-                    ptr.get_ptr() as usize
-                }
-
-                pub(crate) fn new(smart_ptr: &$crate::unsafe_send::SmartPointerSendable<$ptr>, runtime: $crate::runtime::ObsRuntime) -> Result<Self, $crate::utils::ObsError> {
-                    use $crate::utils::ObsString;
-                    let smart_ptr = smart_ptr.clone();
-                    let smart_ptr_as_key = Self::smart_ptr_to_key(&smart_ptr);
+            impl $crate::signals::SignalManagerConnector for $name {
+                type Pointer = $ptr;
 
+                fn insert_senders(key: usize) -> Result<(), $crate::utils::ObsError> {
                     $(
-                        let senders = [<$signal_name:snake:upper _SENDERS>].clone();
-                        let senders = senders.write();
+                        let senders = [<$signal_name:snake:upper _SENDERS>].write();
                         if senders.is_err() {
                             return Err($crate::utils::ObsError::LockError("Failed to acquire write lock for signal senders".to_string()));
                         }
@@ -181,9 +189,28 @@ macro_rules! impl_signal_manager {
                         let (tx, [<_ $signal_name:snake _rx>]) = tokio::sync::broadcast::channel(16);
                         let mut senders = senders.unwrap();
                         // Its fine since we are just using the pointer as key
-                        senders.insert(smart_ptr_as_key.clone(), tx);
+                        senders.insert(key, tx);
                     )*
 
+                    Ok(())
+                }
+
+                fn remove_senders(key: usize) {
+                    $(
+                        match [<$signal_name:snake:upper _SENDERS>].write() {
+                            Ok(mut senders) => {
+                                senders.remove(&key);
+                            }
+                            Err(_) => log::warn!("Failed to acquire write lock for signal {} senders", stringify!($signal_name)),
+                        }
+                    )*
+                }
+
+                fn connect(smart_ptr: &$crate::unsafe_send::SmartPointerSendable<$ptr>, runtime: &$crate::runtime::ObsRuntime) -> Result<(), $crate::utils::ObsError> {
+                    use $crate::utils::ObsString;
+                    let smart_ptr_as_key = Self::smart_ptr_to_key(smart_ptr);
+                    let smart_ptr = smart_ptr.clone();
+
                     $crate::run_with_obs!(runtime, (smart_ptr_as_key, smart_ptr), move || {
                             let handler = ($handler_getter)(smart_ptr);
                             $(
@@ -199,8 +226,40 @@ macro_rules! impl_signal_manager {
                                     );
                                 };
                             )*
-                    })?;
+                    })
+                }
 
+                fn disconnect(ptr: &$crate::unsafe_send::SmartPointerSendable<$ptr>, runtime: &$crate::runtime::ObsRuntime) -> Result<(), $crate::utils::ObsError> {
+                    let ptr = ptr.clone();
+                    $crate::run_with_obs!(runtime, (ptr), move || {
+                        #[allow(unused_variables)]
+                        let handler = ($handler_getter)(ptr.clone());
+                        $(
+                            let signal = $crate::utils::ObsString::new($signal_name);
+                            unsafe {
+                                // Safety: We are in the runtime, the signal string is allocated, we still have the drop guard as ptr in this scope so the handler is valid.
+                                libobs::signal_handler_disconnect(
+                                    handler,
+                                    signal.as_ptr().0,
+                                    Some([< $signal_name:snake _handler>]),
+                                    ptr.get_ptr() as *mut std::ffi::c_void,
+                                );
+                            }
+                        )*
+                    })
+                }
+            }
+
+            impl $name {
+                fn smart_ptr_to_key(ptr: &$crate::unsafe_send::SmartPointerSendable<$ptr>) -> usize {
+                    ptr.get_ptr() as usize
+                }
+
+                pub(crate) fn new(smart_ptr: &$crate::unsafe_send::SmartPointerSendable<$ptr>, runtime: $crate::runtime::ObsRuntime) -> Result<Self, $crate::utils::ObsError> {
+                    let smart_ptr = smart_ptr.clone();
+                    let smart_ptr_as_key = Self::smart_ptr_to_key(&smart_ptr);
+
+                    [<$name:snake:upper _REGISTRY>].acquire::<Self>(smart_ptr_as_key, &smart_ptr, &runtime)?;
                     Ok(Self {
                         pointer: smart_ptr,
                         runtime
@@ -230,43 +289,9 @@ macro_rules! impl_signal_manager {
                 fn drop(&mut self) {
                     log::trace!("Dropping signal manager {}...", stringify!($name));
 
-                    #[allow(unused_variables)]
-                    let ptr = self.pointer.clone();
-                    #[allow(unused_variables)]
-                    let runtime = self.runtime.clone();
-
+                    let key = Self::smart_ptr_to_key(&self.pointer);
                     //TODO make this non blocking
-                    let future = $crate::run_with_obs!(runtime, (ptr), move || {
-                        #[allow(unused_variables)]
-                        let handler = ($handler_getter)(ptr.clone());
-                        $(
-                            let signal = $crate::utils::ObsString::new($signal_name);
-                            unsafe {
-                                // Safety: We are in the runtime, the signal string is allocated, we still have the drop guard as ptr in this scope so the handler is valid.
-                                libobs::signal_handler_disconnect(
-                                    handler,
-                                    signal.as_ptr().0,
-                                    Some([< $signal_name:snake _handler>]),
-                                    ptr.get_ptr() as *mut std::ffi::c_void,
-                                );
-                            }
-                        )*
-                    });
-
-                    let r = {
-                        $(
-                            let handlers = [<$signal_name:snake:upper _SENDERS>].write();
-                            if handlers.is_err() {
-                                log::warn!("Failed to acquire write lock for signal {} senders during drop", stringify!($signal_name));
-                                return;
-                            }
-
-                            let mut handlers = handlers.unwrap();
-                            handlers.remove(&Self::smart_ptr_to_key(&self.pointer));
-                        )*
-
-                        future
-                    };
+                    let r = [<$name:snake:upper _REGISTRY>].release::<Self>(key, &self.pointer, &self.runtime);
 
                     if std::thread::panicking() {
                         return;
@@ -0,0 +1,198 @@
+//! Keeps track of the signal managers of every object.
+//!
+//! The senders of a signal are stored in a global map keyed by the raw pointer of the object,
+//! because OBS only passes that key to the signal handlers. Wrappers of the same object (for
+//! example the scene items returned by `enum_scene_items`) therefore have to share their senders,
+//! otherwise dropping one wrapper would remove the senders and disconnect the handlers of the others.
+//! The handlers of an object are connected by its first manager and disconnected by its last one.
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::{runtime::ObsRuntime, unsafe_send::SmartPointerSendable, utils::ObsError};
+
+/// The operations of a signal manager that the registry needs, implemented by `impl_signal_manager`.
+#[doc(hidden)]
+pub trait SignalManagerConnector {
+    type Pointer: Clone;
+
+    /// Creates the senders of every signal of the object.
+    fn insert_senders(key: usize) -> Result<(), ObsError>;
+
+    /// Removes the senders of every signal of the object, which closes all receivers.
+    fn remove_senders(key: usize);
+
+    /// Connects the signal handlers of the object in OBS.
+    fn connect(
+        pointer: &SmartPointerSendable<Self::Pointer>,
+        runtime: &ObsRuntime,
+    ) -> Result<(), ObsError>;
+
+    /// Disconnects the signal handlers of the object in OBS.
+    fn disconnect(
+        pointer: &SmartPointerSendable<Self::Pointer>,
+        runtime: &ObsRuntime,
+    ) -> Result<(), ObsError>;
+}
+
+#[derive(Debug, Default)]
+struct ObjectState {
+    /// How many managers of the object exist
+    managers: usize,
+    /// Whether the signal handlers of the object are connected in OBS
+    connected: bool,
+    /// Whether a thread is connecting or disconnecting the handlers right now
+    busy: bool,
+}
+
+/// Counts the signal managers of every object of one manager type.
+///
+/// The lock is never held while the handlers are connected or disconnected, as that waits for the
+/// OBS thread, which might create or drop managers itself. Instead, the thread that changes the count
+/// first becomes responsible for the handlers and keeps connecting or disconnecting them until they
+/// match the count, so changes made by other threads in the meantime are picked up as well.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct SignalManagerRegistry {
+    objects: Mutex<HashMap<usize, ObjectState>>,
+}
+
+impl SignalManagerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a new manager of the object with the given key and connects its handlers
+    /// if it is the first one.
+    pub fn acquire<C: SignalManagerConnector>(
+        &self,
+        key: usize,
+        pointer: &SmartPointerSendable<C::Pointer>,
+        runtime: &ObsRuntime,
+    ) -> Result<(), ObsError> {
+        {
+            let mut objects = self.lock()?;
+            match objects.entry(key) {
+                Entry::Occupied(mut entry) => entry.get_mut().managers += 1,
+                Entry::Vacant(entry) => {
+                    if let Err(e) = C::insert_senders(key) {
+                        C::remove_senders(key);
+                        return Err(e);
+                    }
+
+                    entry.insert(ObjectState {
+                        managers: 1,
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+
+        let result = self.sync::<C>(key, pointer, runtime);
+        if result.is_err() {
+            // The manager is not created, so it must not be counted
+            let mut objects = self.lock()?;
+            if let Some(state) = objects.get_mut(&key) {
+                state.managers = state.managers.saturating_sub(1);
+                Self::remove_if_unused::<C>(&mut objects, key);
+            }
+        }
+
+        result
+    }
+
+    /// Unregisters a manager of the object with the given key and disconnects its handlers
+    /// if it was the last one.
+    pub fn release<C: SignalManagerConnector>(
+        &self,
+        key: usize,
+        pointer: &SmartPointerSendable<C::Pointer>,
+        runtime: &ObsRuntime,
+    ) -> Result<(), ObsError> {
+        {
+            let mut objects = self.lock()?;
+            if let Some(state) = objects.get_mut(&key) {
+                state.managers = state.managers.saturating_sub(1);
+            }
+        }
+
+        self.sync::<C>(key, pointer, runtime)
+    }
+
+    /// Connects or disconnects the handlers until they match the number of managers,
+    /// unless another thread is doing so already.
+    fn sync<C: SignalManagerConnector>(
+        &self,
+        key: usize,
+        pointer: &SmartPointerSendable<C::Pointer>,
+        runtime: &ObsRuntime,
+    ) -> Result<(), ObsError> {
+        {
+            let mut objects = self.lock()?;
+            match objects.get_mut(&key) {
+                Some(state) if !state.busy => state.busy = true,
+                // The thread that is busy picks up the new count
+                _ => return Ok(()),
+            }
+        }
+
+        loop {
+            let connect = {
+                let mut objects = self.lock()?;
+                let Some(state) = objects.get_mut(&key) else {
+                    return Ok(());
+                };
+
+                let connect = state.managers > 0;
+                if connect == state.connected {
+                    state.busy = false;
+                    Self::remove_if_unused::<C>(&mut objects, key);
+                    return Ok(());
+                }
+
+                connect
+            };
+
+            let result = if connect {
+                C::connect(pointer, runtime)
+            } else {
+                C::disconnect(pointer, runtime)
+            };
+
+            let mut objects = self.lock()?;
+            let Some(state) = objects.get_mut(&key) else {
+                return result;
+            };
+
+            if let Err(e) = result {
+                state.busy = false;
+                Self::remove_if_unused::<C>(&mut objects, key);
+                return Err(e);
+            }
+
+            state.connected = connect;
+        }
+    }
+
+    fn remove_if_unused<C: SignalManagerConnector>(
+        objects: &mut MutexGuard<HashMap<usize, ObjectState>>,
+        key: usize,
+    ) {
+        let unused = objects
+            .get(&key)
+            .is_some_and(|state| state.managers == 0 && !state.connected && !state.busy);
+
+        if unused {
+            objects.remove(&key);
+            C::remove_senders(key);
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, HashMap<usize, ObjectState>>, ObsError> {
+        self.objects.lock().map_err(|_| {
+            ObsError::LockError("Failed to acquire lock for signal manager counts".to_string())
+        })
+    }
+}
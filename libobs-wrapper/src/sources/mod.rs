@@ -12,6 +12,9 @@ mod macros;
 mod filter;
 pub use filter::*;
 
+mod transition;
+pub use transition::*;
+
 use libobs::obs_source_t;
 
 use crate::{
@@ -25,7 +28,10 @@ use crate::{
     utils::{ObsDropGuard, ObsError, ObsString, SourceInfo},
 };
 
-use std::sync::{Arc, RwLock};
+use std::{
+    ffi::CStr,
+    sync::{Arc, RwLock},
+};
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -137,6 +143,81 @@ impl ObsSourceRef {
             signal_manager: Arc::new(signals),
         })
     }
+
+    /// Wraps a source that already exists in OBS, for example the source of a scene item
+    /// that was loaded from a scene collection or created by a plugin.
+    /// A new reference to the source is taken, so the source stays alive as long as this struct.
+    pub(crate) fn new_from_raw(
+        source: Sendable<*mut obs_source_t>,
+        runtime: ObsRuntime,
+    ) -> Result<Self, ObsError> {
+        let (source_ptr, id, name, settings_ptr, hotkey_data_ptr) =
+            run_with_obs!(runtime, (source), move || {
+                let source_ptr = unsafe {
+                    // Safety: The caller must make sure that the source pointer is valid.
+                    libobs::obs_source_get_ref(source.0)
+                };
+
+                if source_ptr.is_null() {
+                    return Err(ObsError::NullPointer(Some(
+                        "Source is being destroyed".into(),
+                    )));
+                }
+
+                let (id, name, settings_ptr, hotkey_data_ptr) = unsafe {
+                    // Safety: We hold a reference to the source, so these pointers are valid while we copy them.
+                    let id = CStr::from_ptr(libobs::obs_source_get_id(source_ptr))
+                        .to_string_lossy()
+                        .to_string();
+                    let name = CStr::from_ptr(libobs::obs_source_get_name(source_ptr))
+                        .to_string_lossy()
+                        .to_string();
+
+                    // The hotkey data is not stored by OBS until the source is saved, so it is collected here
+                    (
+                        id,
+                        name,
+                        libobs::obs_source_get_settings(source_ptr),
+                        libobs::obs_hotkeys_save_source(source_ptr),
+                    )
+                };
+
+                Ok((
+                    Sendable(source_ptr),
+                    id,
+                    name,
+                    Sendable(settings_ptr),
+                    Sendable(hotkey_data_ptr),
+                ))
+            })??;
+
+        let source_ptr = SmartPointerSendable::new(
+            source_ptr.0,
+            Arc::new(_ObsSourceGuard {
+                source: source_ptr.clone(),
+                runtime: runtime.clone(),
+            }),
+        );
+
+        let settings = ImmutableObsData::from_raw_pointer(settings_ptr, runtime.clone());
+        let hotkey_data = if hotkey_data_ptr.0.is_null() {
+            ImmutableObsData::new(&runtime)?
+        } else {
+            ImmutableObsData::from_raw_pointer(hotkey_data_ptr, runtime.clone())
+        };
+
+        let signals = ObsSourceSignals::new(&source_ptr, runtime.clone())?;
+        Ok(Self {
+            source: source_ptr,
+            id: ObsString::new(id),
+            name: ObsString::new(name),
+            settings: Arc::new(RwLock::new(settings)),
+            hotkey_data: Arc::new(RwLock::new(hotkey_data)),
+            attached_filters: Arc::new(RwLock::new(Vec::new())),
+            runtime,
+            signal_manager: Arc::new(signals),
+        })
+    }
 }
 
 impl ObsObjectTraitPrivate for ObsSourceRef {
@@ -1,7 +1,9 @@
 use crate::{
     data::object::ObsObjectTrait,
+    enums::ObsAudioTracks,
     macros::impl_eq_of_ptr,
-    sources::{ObsFilterRef, ObsSourceSignals, _ObsRemoveFilterOnDrop},
+    run_with_obs,
+    sources::{_ObsRemoveFilterOnDrop, ObsFilterRef, ObsSourceSignals},
     utils::ObsError,
 };
 use std::sync::Arc;
@@ -35,6 +37,26 @@ pub trait ObsSourceTrait: ObsObjectTrait<*mut libobs::obs_source_t> {
 
     fn get_active_filters(&self) -> Result<Vec<ObsFilterGuardPair>, ObsError>;
     fn apply_filter(&self, filter: &ObsFilterRef) -> Result<(), ObsError>;
+
+    /// Sets the audio tracks this source is mixed into. New sources are mixed into every track.
+    fn set_audio_mixers(&self, tracks: ObsAudioTracks) -> Result<(), ObsError> {
+        let source_ptr = self.as_ptr();
+        run_with_obs!(self.runtime(), (source_ptr), move || unsafe {
+            // Safety: The source pointer is valid because of the smart pointer.
+            libobs::obs_source_set_audio_mixers(source_ptr.get_ptr(), tracks.bits());
+        })
+    }
+
+    /// Returns the audio tracks this source is mixed into.
+    fn get_audio_mixers(&self) -> Result<ObsAudioTracks, ObsError> {
+        let source_ptr = self.as_ptr();
+        let mixers = run_with_obs!(self.runtime(), (source_ptr), move || unsafe {
+            // Safety: The source pointer is valid because of the smart pointer.
+            libobs::obs_source_get_audio_mixers(source_ptr.get_ptr())
+        })?;
+
+        Ok(ObsAudioTracks::from_bits_truncate(mixers))
+    }
 }
 
 impl_eq_of_ptr!(dyn ObsSourceTrait);
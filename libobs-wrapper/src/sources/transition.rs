@@ -0,0 +1,93 @@
+use std::{convert::Infallible, str::FromStr};
+
+use crate::utils::ObsString;
+
+/// The transitions that are shipped with OBS, most of them by the `obs-transitions` module.
+/// Transitions of other plugins can be used with `Other`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObsTransitionType {
+    /// Switches instantly. From libobs itself, so it is always available.
+    Cut,
+    /// From plugin: `obs-transitions`
+    Fade,
+    /// From plugin: `obs-transitions`
+    Swipe,
+    /// From plugin: `obs-transitions`
+    Slide,
+    /// From plugin: `obs-transitions`
+    Stinger,
+    /// From plugin: `obs-transitions`
+    FadeToColor,
+    /// Luma wipe. From plugin: `obs-transitions`
+    Wipe,
+    Other(String),
+}
+
+impl ObsTransitionType {
+    /// Returns the id of the source type of this transition.
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Cut => "cut_transition",
+            Self::Fade => "fade_transition",
+            Self::Swipe => "swipe_transition",
+            Self::Slide => "slide_transition",
+            Self::Stinger => "obs_stinger_transition",
+            Self::FadeToColor => "fade_to_color_transition",
+            Self::Wipe => "wipe_transition",
+            Self::Other(id) => id,
+        }
+    }
+}
+
+impl FromStr for ObsTransitionType {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "cut_transition" => Self::Cut,
+            "fade_transition" => Self::Fade,
+            "swipe_transition" => Self::Swipe,
+            "slide_transition" => Self::Slide,
+            "obs_stinger_transition" => Self::Stinger,
+            "fade_to_color_transition" => Self::FadeToColor,
+            "wipe_transition" => Self::Wipe,
+            e => Self::Other(e.to_string()),
+        })
+    }
+}
+
+impl From<ObsTransitionType> for ObsString {
+    fn from(v: ObsTransitionType) -> ObsString {
+        ObsString::new(v.id())
+    }
+}
+
+/// Selects whether the transition played when showing or the one played when hiding a scene item is meant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObsSceneItemTransition {
+    Show,
+    Hide,
+}
+
+impl ObsSceneItemTransition {
+    pub(crate) fn is_show(&self) -> bool {
+        *self == Self::Show
+    }
+}
+
+#[test]
+fn test_transition_type_id_roundtrip() {
+    for transition in [
+        ObsTransitionType::Cut,
+        ObsTransitionType::Fade,
+        ObsTransitionType::Swipe,
+        ObsTransitionType::Slide,
+        ObsTransitionType::Stinger,
+        ObsTransitionType::FadeToColor,
+        ObsTransitionType::Wipe,
+        ObsTransitionType::Other("move_transition".to_string()),
+    ] {
+        let parsed = ObsTransitionType::from_str(transition.id()).unwrap();
+        assert_eq!(parsed, transition);
+    }
+}
@@ -93,6 +93,42 @@ impl CalldataWrapper {
         Ok(value)
     }
 
+    /// Extracts a bool for the given key from the calldata.
+    pub fn get_bool<T: Into<ObsString>>(&mut self, key: T) -> Result<bool, ObsError> {
+        let key: ObsString = key.into();
+        let self_ptr = unsafe {
+            // Safety: We won't modify the calldata, so it's safe to get a mutable pointer here.
+            self.as_mut_ptr()
+        };
+
+        let _drop_guard = self._drop_guard.clone(); // Ensure runtime is valid during the call
+        let value = run_with_obs!(
+            self.runtime.clone(),
+            (_drop_guard, self_ptr, key),
+            move || {
+                let mut data = false;
+                let ok = unsafe {
+                    // Safety: self_ptr and key are valid pointers and data has the size we pass.
+                    libobs::calldata_get_data(
+                        self_ptr.0,
+                        key.as_ptr().0,
+                        &mut data as *mut bool as *mut std::ffi::c_void,
+                        std::mem::size_of::<bool>(),
+                    )
+                };
+                if !ok {
+                    return Err(ObsError::Unexpected(format!(
+                        "Calldata bool {key} not found."
+                    )));
+                }
+
+                Ok(data)
+            }
+        )??;
+
+        Ok(value)
+    }
+
     //TODO implement calldata get_data type but I think this is hard to safely do this
 }
 
@@ -115,6 +151,18 @@ pub trait ObsCalldataExt {
         proc_handler: &Sendable<*mut proc_handler_t>,
         name: T,
     ) -> Result<CalldataWrapper, ObsError>;
+
+    /// Calls the procedure with the given string parameters, for example
+    /// `[("chapter_name", "Intro")]` for the `add_chapter` procedure.
+    ///
+    /// # Safety
+    /// Make sure that the proc_handler pointer is valid.
+    unsafe fn call_proc_handler_with_strings<T: Into<ObsString>>(
+        &self,
+        proc_handler: &Sendable<*mut proc_handler_t>,
+        name: T,
+        params: &[(&str, &str)],
+    ) -> Result<CalldataWrapper, ObsError>;
 }
 
 impl ObsCalldataExt for ObsRuntime {
@@ -122,6 +170,15 @@ impl ObsCalldataExt for ObsRuntime {
         &self,
         proc_handler: &Sendable<*mut proc_handler_t>,
         name: T,
+    ) -> Result<CalldataWrapper, ObsError> {
+        self.call_proc_handler_with_strings(proc_handler, name, &[])
+    }
+
+    unsafe fn call_proc_handler_with_strings<T: Into<ObsString>>(
+        &self,
+        proc_handler: &Sendable<*mut proc_handler_t>,
+        name: T,
+        params: &[(&str, &str)],
     ) -> Result<CalldataWrapper, ObsError> {
         if proc_handler.0.is_null() {
             return Err(ObsError::NullPointer(None));
@@ -129,16 +186,40 @@ impl ObsCalldataExt for ObsRuntime {
 
         let proc_handler = proc_handler.clone();
         let name: ObsString = name.into();
-        let mut calldata = run_with_obs!(self.clone(), (proc_handler, name), move || {
+        let params: Vec<(ObsString, ObsString)> = params
+            .iter()
+            .map(|(key, value)| (ObsString::new(key), ObsString::new(value)))
+            .collect();
+        let mut calldata = run_with_obs!(self.clone(), (proc_handler, name, params), move || {
             // Safety: calldata will be properly freed by the drop guard, and we are using a struct for the `zeroed` call.
             let data: calldata_t = unsafe { std::mem::zeroed() };
             let mut data = Box::pin(data);
             // Safety: Data will not get moved out of the pinned box, only the proc handler call will use the pointer and not move it.
             let raw_ptr = unsafe { Pin::as_mut(&mut data).get_unchecked_mut() };
 
+            for (key, value) in params.iter() {
+                unsafe {
+                    // Safety: The calldata copies the value including its nul terminator and both strings
+                    // are valid C strings, just like calldata_set_string of libobs does it.
+                    let value_len = CStr::from_ptr(value.as_ptr().0).to_bytes_with_nul().len();
+                    libobs::calldata_set_data(
+                        raw_ptr,
+                        key.as_ptr().0,
+                        value.as_ptr().0 as *const std::ffi::c_void,
+                        value_len,
+                    );
+                }
+            }
+
             // Safety: the caller must have made sure that the proc handler is valid, the name pointer and the raw_ptr of the calldata is valid.
             let ok = unsafe { libobs::proc_handler_call(proc_handler.0, name.as_ptr().0, raw_ptr) };
             if !ok {
+                // The parameters might have been allocated already
+                unsafe {
+                    // Safety: We are in the runtime and the calldata is valid.
+                    calldata_free(raw_ptr);
+                }
+
                 return Err(ObsError::Unexpected(
                     "Couldn't call proc handler".to_string(),
                 ));
@@ -172,4 +253,14 @@ impl ObsCalldataExt for ObsContext {
     ) -> Result<CalldataWrapper, ObsError> {
         self.runtime().call_proc_handler(proc_handler, name)
     }
+
+    unsafe fn call_proc_handler_with_strings<T: Into<ObsString>>(
+        &self,
+        proc_handler: &Sendable<*mut proc_handler_t>,
+        name: T,
+        params: &[(&str, &str)],
+    ) -> Result<CalldataWrapper, ObsError> {
+        self.runtime()
+            .call_proc_handler_with_strings(proc_handler, name, params)
+    }
 }